@@ -1,39 +1,32 @@
 advent_of_code::solution!(1);
 
-#[cfg(not(debug_assertions))]
-const SIZE: usize = 1000;
-#[cfg(not(debug_assertions))]
-const NUM_SIZE: usize = 5;
-#[cfg(not(debug_assertions))]
-const ASCII_CONVERSION_FACTOR: usize = 533328; // 48 * 11_111: 48 * ('1'*(NUMSIZE+1))
-
-#[cfg(debug_assertions)]
-const SIZE: usize = 6;
-#[cfg(debug_assertions)]
-const NUM_SIZE: usize = 1;
-#[cfg(debug_assertions)]
-const ASCII_CONVERSION_FACTOR: usize = 48; // 48 * 1: 48 * ('1'*(NUMSIZE+1))
-
-const OFFSET_NEXT_NUM: usize = NUM_SIZE + 3;
 const MAX_NUM: usize = 100000;
 
+/// Number of lines and number of digits of each number.
+fn sizes(input: &str) -> (usize, usize) {
+    (input.lines().count(), input.find(' ').unwrap_or(0))
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    let mut left = [0i32; SIZE];
-    let mut right = [0i32; SIZE];
+    let (size, num_size) = sizes(input);
+    let offset_next_num = num_size + 3;
+
+    let mut left = vec![0i32; size];
+    let mut right = vec![0i32; size];
 
     unsafe {
         let bytes = input.as_bytes();
         let mut i = 0;
-        for j in 0..SIZE {
+        for j in 0..size {
             // Parse first number
             let mut l = 0;
-            for c in bytes.get_unchecked(i..i + NUM_SIZE) {
+            for c in bytes.get_unchecked(i..i + num_size) {
                 l = l * 10 + *c as i32;
             }
 
             // Parse second number
             let mut r = 0;
-            for c in bytes.get_unchecked(i + OFFSET_NEXT_NUM..i + OFFSET_NEXT_NUM + NUM_SIZE) {
+            for c in bytes.get_unchecked(i + offset_next_num..i + offset_next_num + num_size) {
                 r = r * 10 + *c as i32;
             }
 
@@ -41,7 +34,7 @@ pub fn part_one(input: &str) -> Option<u32> {
             *left.get_unchecked_mut(j) = l;
             *right.get_unchecked_mut(j) = r;
             // Advance to next row
-            i += OFFSET_NEXT_NUM + NUM_SIZE + 1;
+            i += offset_next_num + num_size + 1;
         }
 
         left.sort_unstable();
@@ -57,33 +50,38 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let mut left = [0usize; SIZE];
+    let (size, num_size) = sizes(input);
+    let offset_next_num = num_size + 3;
+    // 48 * 11_111 for 5 digits: 48 * ('1' * num_size)
+    let ascii_conversion_factor = 48 * (10usize.pow(num_size as u32) - 1) / 9;
+
+    let mut left = vec![0usize; size];
     let mut right = [0usize; MAX_NUM];
 
     unsafe {
         let bytes = input.as_bytes();
         let mut i = 0;
-        for idx in 0..SIZE {
+        for idx in 0..size {
             // Parse first number
             let mut l = 0;
-            for c in bytes.get_unchecked(i..i + NUM_SIZE) {
+            for c in bytes.get_unchecked(i..i + num_size) {
                 l = l * 10 + *c as usize;
             }
-            l -= ASCII_CONVERSION_FACTOR;
+            l -= ascii_conversion_factor;
 
             // Parse second number
             let mut r = 0;
-            for c in bytes.get_unchecked(i + OFFSET_NEXT_NUM..i + OFFSET_NEXT_NUM + NUM_SIZE) {
+            for c in bytes.get_unchecked(i + offset_next_num..i + offset_next_num + num_size) {
                 r = r * 10 + *c as usize;
             }
-            r -= ASCII_CONVERSION_FACTOR; // 533328
+            r -= ascii_conversion_factor;
 
             // Add to vectors and HashMap
             *left.get_unchecked_mut(idx) = l;
             *right.get_unchecked_mut(r) += r;
 
             // Advance to next row
-            i += OFFSET_NEXT_NUM + NUM_SIZE + 1;
+            i += offset_next_num + num_size + 1;
         }
 
         Some(left.iter().map(|v| *right.get_unchecked(*v)).sum::<usize>() as u32)
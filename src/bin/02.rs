@@ -1,4 +1,4 @@
-use advent_of_code::parse_u32;
+use advent_of_code::Scanner;
 
 advent_of_code::solution!(2);
 
@@ -12,14 +12,12 @@ pub fn part_one(input: &str) -> Option<u32> {
                 let mut increasing = false;
                 let mut decreasing = false;
 
-                let bytes = line.as_bytes();
+                let mut scanner = Scanner::from(*line);
+                let mut levels = scanner.uints::<u32>();
                 // pars the first number
-                let mut i = 0;
-                let mut prev = parse_u32(bytes, &mut i);
-
-                while i < bytes.len() {
-                    let c = parse_u32(bytes, &mut i);
+                let mut prev = levels.next().unwrap();
 
+                for c in levels {
                     let diff = c.abs_diff(prev);
                     if !(1..=3).contains(&diff) {
                         return false;
@@ -67,17 +65,17 @@ fn is_valid(bytes: &[u8]) -> (bool, Vec<u32>) {
     let mut increasing = false;
     let mut decreasing = false;
 
-    let mut i = 0;
+    let mut scanner = Scanner::new(bytes);
+    let mut numbers = scanner.uints::<u32>();
 
-    let mut prev = parse_u32(bytes, &mut i);
+    let mut prev = numbers.next().unwrap();
     let mut levels = Vec::with_capacity(20);
     levels.push(0);
 
     let mut correct = true;
 
     // Use unsafe to directly access raw pointers for faster iteration
-    while i < bytes.len() {
-        let current = parse_u32(bytes, &mut i);
+    for current in numbers {
         levels.push(current);
 
         let diff = prev.abs_diff(current);
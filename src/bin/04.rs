@@ -1,16 +1,13 @@
 advent_of_code::solution!(4);
 
-#[cfg(not(debug_assertions))]
-const GRID_SIZE: i32 = 140;
-
-#[cfg(debug_assertions)]
-const GRID_SIZE: i32 = 10;
-
-const UGRID_SIZE: usize = GRID_SIZE as usize;
+/// Width of the square grid, i.e. the length of its first line.
+fn grid_size(input: &str) -> i32 {
+    input.find('\n').unwrap_or(input.len()) as i32
+}
 
-fn convert_input_to_cells(input: &str) -> [u8; UGRID_SIZE * UGRID_SIZE] {
+fn convert_input_to_cells(input: &str, grid_size: i32) -> Vec<u8> {
     unsafe {
-        let mut cells = [0u8; UGRID_SIZE * UGRID_SIZE];
+        let mut cells = vec![0u8; (grid_size * grid_size) as usize];
         let bytes = input.as_bytes();
 
         let mut idx = 0;
@@ -30,7 +27,8 @@ fn get_chars_at_offsets(
     x: i32,
     y: i32,
     offsets: &[(i32, i32)],
-    cells: &[u8; UGRID_SIZE * UGRID_SIZE],
+    cells: &[u8],
+    grid_size: i32,
     chars: &mut [u8],
 ) {
     unsafe {
@@ -39,8 +37,8 @@ fn get_chars_at_offsets(
             let yo = y + dy;
 
             // Perform bounds check manually
-            chars[j] = if xo >= 0 && xo < GRID_SIZE && yo >= 0 && yo < GRID_SIZE {
-                *cells.get_unchecked((xo * GRID_SIZE + yo) as usize)
+            chars[j] = if xo >= 0 && xo < grid_size && yo >= 0 && yo < grid_size {
+                *cells.get_unchecked((xo * grid_size + yo) as usize)
             } else {
                 0 // Default value for out-of-bounds
             };
@@ -49,7 +47,8 @@ fn get_chars_at_offsets(
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let cells = convert_input_to_cells(input);
+    let grid_size = grid_size(input);
+    let cells = convert_input_to_cells(input, grid_size);
 
     const OFFSETS_GROUPS: [[(i32, i32); 4]; 4] = [
         [(-2, 0), (-1, 0), (0, 0), (1, 0)],
@@ -59,11 +58,11 @@ pub fn part_one(input: &str) -> Option<u32> {
     ];
 
     let mut xmasses = 0;
-    for x in 0..GRID_SIZE {
-        for y in 0..GRID_SIZE {
+    for x in 0..grid_size {
+        for y in 0..grid_size {
             for offsets in OFFSETS_GROUPS.iter() {
                 let mut chars = [0u8; 4];
-                get_chars_at_offsets(x, y, offsets, &cells, &mut chars);
+                get_chars_at_offsets(x, y, offsets, &cells, grid_size, &mut chars);
 
                 if chars == [b'X', b'M', b'A', b'S'] || chars == [b'S', b'A', b'M', b'X'] {
                     xmasses += 1;
@@ -76,19 +75,20 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let cells = convert_input_to_cells(input);
+    let grid_size = grid_size(input);
+    let cells = convert_input_to_cells(input, grid_size);
 
     const OFFSETS_GROUPS: [[(i32, i32); 3]; 2] =
         [[(-1, 1), (0, 0), (1, -1)], [(1, 1), (0, 0), (-1, -1)]];
 
     let mut xmasses = 0;
-    for x in 0..GRID_SIZE {
-        for y in 0..GRID_SIZE {
+    for x in 0..grid_size {
+        for y in 0..grid_size {
             if OFFSETS_GROUPS
                 .iter()
                 .filter(|offsets| {
                     let mut chars = [0u8; 3];
-                    get_chars_at_offsets(x, y, *offsets, &cells, &mut chars);
+                    get_chars_at_offsets(x, y, *offsets, &cells, grid_size, &mut chars);
 
                     chars == [b'M', b'A', b'S'] || chars == [b'S', b'A', b'M']
                 })
@@ -2,16 +2,6 @@ use rayon::prelude::*;
 
 advent_of_code::solution!(5);
 
-#[cfg(not(debug_assertions))]
-const PAGES_COUNT: usize = 199;
-#[cfg(debug_assertions)]
-const PAGES_COUNT: usize = 6;
-
-#[cfg(not(debug_assertions))]
-const RULES_COUNT: usize = 1176;
-#[cfg(debug_assertions)]
-const RULES_COUNT: usize = 21;
-
 const MAX_VALUE: usize = 99;
 
 pub fn part_one(input: &str) -> Option<u32> {
@@ -63,15 +53,18 @@ pub fn part_two(input: &str) -> Option<u32> {
     )
 }
 
-fn parse_input(input: &str) -> ([(usize, usize); RULES_COUNT], [Vec<usize>; PAGES_COUNT]) {
+fn parse_input(input: &str) -> (Vec<(usize, usize)>, Vec<Vec<usize>>) {
+    let (rules, updates) = input.split_once("\n\n").unwrap_or((input, ""));
+    let (rules_count, pages_count) = (rules.lines().count(), updates.lines().count());
+
     let bytes = input.as_bytes();
-    let mut rules = [(0usize, 0usize); RULES_COUNT];
-    let mut pages = [const { Vec::new() }; PAGES_COUNT];
+    let mut rules = vec![(0usize, 0usize); rules_count];
+    let mut pages = vec![Vec::new(); pages_count];
 
     // Faster parsing using unchecked indexing and direct byte conversion
     unsafe {
         let mut i = 0;
-        for idx in 0..RULES_COUNT {
+        for idx in 0..rules_count {
             // Direct fast byte to number conversion
             let a = ((bytes.get_unchecked(i) - b'0') * 10 + (bytes.get_unchecked(i + 1) - b'0'))
                 as usize;
@@ -84,7 +77,7 @@ fn parse_input(input: &str) -> ([(usize, usize); RULES_COUNT], [Vec<usize>; PAGE
 
         i += 1;
 
-        for idx in 0..PAGES_COUNT {
+        for idx in 0..pages_count {
             let page_vec = pages.get_unchecked_mut(idx);
 
             loop {
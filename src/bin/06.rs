@@ -12,12 +12,12 @@ enum GuardDirections {
 }
 
 impl GuardDirections {
-    fn to_offset(&self) -> i32 {
+    fn to_offset(&self, grid_size: usize) -> i32 {
         match self {
-            GuardDirections::UP => -(GRID_SIZE as i32 + 1), //Position { y: -1, x: 0 },
+            GuardDirections::UP => -(grid_size as i32 + 1), //Position { y: -1, x: 0 },
             GuardDirections::LEFT => -1,                    //Position { y: 0, x: -1 },
             GuardDirections::RIGHT => 1,                    //Position { y: 0, x: 1 },
-            GuardDirections::DOWN => GRID_SIZE as i32 + 1,  //Position { y: 1, x: 0 },
+            GuardDirections::DOWN => grid_size as i32 + 1,  //Position { y: 1, x: 0 },
         }
     }
 
@@ -37,34 +37,28 @@ struct Guard {
     pub position: i32,
 }
 
-#[cfg(not(debug_assertions))]
-const GRID_SIZE: usize = 130;
-#[cfg(not(debug_assertions))]
-const INITIAL_POSITION: i32 = (84 * (GRID_SIZE + 1) + 89) as i32;
-
-#[cfg(debug_assertions)]
-const GRID_SIZE: usize = 10;
-#[cfg(debug_assertions)]
-const INITIAL_POSITION: i32 = (6 * (GRID_SIZE + 1) + 4) as i32;
-
-const MAX_INDEX: usize = GRID_SIZE * GRID_SIZE + GRID_SIZE + 1;
+/// Size of the grid and the position the guard starts at.
+fn layout(input: &str) -> (usize, i32) {
+    let grid_size = input.find('\n').unwrap_or(input.len());
+    (grid_size, input.find('^').unwrap_or(0) as i32)
+}
 
-fn guard_move(bytes: &[u8]) -> Option<(i32, [i32; MAX_INDEX])> {
-    let mut visited = [0i32; MAX_INDEX];
+fn guard_move(bytes: &[u8], grid_size: usize, initial_position: i32) -> Option<(i32, Vec<i32>)> {
+    let mut visited = vec![0i32; grid_size * grid_size + grid_size + 1];
     let mut count = 0;
 
-    // guard starts at INITIAL_POSITION going up
+    // guard starts at initial_position going up
     let mut guard = Guard {
         direction: GuardDirections::UP,
-        position: INITIAL_POSITION,
+        position: initial_position,
     };
 
     loop {
         unsafe {
-            let offset = guard.direction.to_offset();
+            let offset = guard.direction.to_offset(grid_size);
             let np = guard.position + offset;
 
-            if np < 0 || np > ((GRID_SIZE + 1) * GRID_SIZE) as i32 {
+            if np < 0 || np > ((grid_size + 1) * grid_size) as i32 {
                 if *visited.get_unchecked(guard.position as usize) == 0 {
                     count += 1;
                     *visited.get_unchecked_mut(guard.position as usize) = guard.position;
@@ -85,7 +79,7 @@ fn guard_move(bytes: &[u8]) -> Option<(i32, [i32; MAX_INDEX])> {
             }
 
             if c != b'#' {
-                let n_offset = guard.direction.to_offset();
+                let n_offset = guard.direction.to_offset(grid_size);
                 let new_pos = guard.position + n_offset;
 
                 if *visited.get_unchecked(guard.position as usize) == 0 {
@@ -105,17 +99,19 @@ fn guard_move(bytes: &[u8]) -> Option<(i32, [i32; MAX_INDEX])> {
 }
 
 pub fn part_one(bytes: &str) -> Option<u32> {
-    let (count, _) = guard_move(bytes.as_bytes())?;
+    let (grid_size, initial_position) = layout(bytes);
+    let (count, _) = guard_move(bytes.as_bytes(), grid_size, initial_position)?;
     Some(count as u32)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
+    let (grid_size, initial_position) = layout(input);
     let bytes = input.as_bytes();
-    let (_, visited) = guard_move(bytes)?;
+    let (_, visited) = guard_move(bytes, grid_size, initial_position)?;
 
     let g = Guard {
         direction: GuardDirections::UP,
-        position: INITIAL_POSITION,
+        position: initial_position,
     };
 
     let loops = visited
@@ -127,12 +123,12 @@ pub fn part_two(input: &str) -> Option<u32> {
 
             unsafe {
                 loop {
-                    if step > GRID_SIZE * GRID_SIZE {
+                    if step > grid_size * grid_size {
                         return true;
                     }
                     step += 1;
 
-                    let offset = guard.direction.to_offset();
+                    let offset = guard.direction.to_offset(grid_size);
                     let np = guard.position + offset;
 
                     if np == cell {
@@ -140,7 +136,7 @@ pub fn part_two(input: &str) -> Option<u32> {
                         continue;
                     }
 
-                    if np < 0 || np > ((GRID_SIZE + 1) * GRID_SIZE) as i32 {
+                    if np < 0 || np > ((grid_size + 1) * grid_size) as i32 {
                         return false;
                     }
 
@@ -151,7 +147,7 @@ pub fn part_two(input: &str) -> Option<u32> {
                     }
 
                     if c != b'#' {
-                        let n_offset = guard.direction.to_offset();
+                        let n_offset = guard.direction.to_offset(grid_size);
                         let new_pos = guard.position + n_offset;
 
                         guard.position = new_pos;
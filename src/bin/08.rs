@@ -1,3 +1,5 @@
+use advent_of_code::BitGrid;
+
 advent_of_code::solution!(8);
 
 fn parse_input(input: &str) -> ([Vec<(i32, i32)>; 255], i32) {
@@ -18,7 +20,7 @@ fn parse_input(input: &str) -> ([Vec<(i32, i32)>; 255], i32) {
 
 pub fn part_one(input: &str) -> Option<u32> {
     let (antennas, grid_size) = parse_input(input);
-    let mut antinodes: [u64; 50] = [0u64; 50];
+    let mut antinodes = BitGrid::new(grid_size as usize, grid_size as usize);
 
     for antennas in antennas {
         for &a in &antennas {
@@ -28,20 +30,20 @@ pub fn part_one(input: &str) -> Option<u32> {
                     let (x, y) = (a.0 + ox, a.1 + oy);
 
                     if x < grid_size && y < grid_size && x >= 0 && y >= 0 {
-                        antinodes[y as usize] |= 1 << x;
+                        antinodes.insert((x as usize, y as usize));
                     }
                 }
             }
         }
     }
 
-    Some(antinodes.iter().map(|v| v.count_ones()).sum())
+    Some(antinodes.count() as u32)
 }
 
 
 pub fn part_two(input: &str) -> Option<u32> {
     let (antennas, grid_size) = parse_input(input);
-    let mut antinodes: [u64; 50] = [0u64; 50];
+    let mut antinodes = BitGrid::new(grid_size as usize, grid_size as usize);
 
     for antennas in antennas.iter().filter(|a| !a.is_empty()) {
         for i in 0..antennas.len() {
@@ -54,7 +56,7 @@ pub fn part_two(input: &str) -> Option<u32> {
                 // Closure for extending the line
                 let mut draw_line = |mut wx: i32, mut wy: i32, dx: i32, dy: i32| {
                     while wx >= 0 && wy >= 0 && wx < grid_size && wy < grid_size {
-                        antinodes[wy as usize] |= 1 << wx;
+                        antinodes.insert((wx as usize, wy as usize));
                         wx += dx;
                         wy += dy;
                     }
@@ -66,7 +68,7 @@ pub fn part_two(input: &str) -> Option<u32> {
             }
         }
     }
-    Some(antinodes.iter().map(|v| v.count_ones()).sum())
+    Some(antinodes.count() as u32)
 }
 
 #[cfg(test)]
@@ -2,8 +2,8 @@
 
 advent_of_code::solution!(9);
 
-use std::cmp::Reverse;
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::collections::HashSet;
+use advent_of_code::RangeSet;
 use itertools::Itertools;
 
 fn parse_input(input: &str) -> Vec<Option<u64>> {
@@ -61,7 +61,20 @@ pub fn part_two(input: &str) -> Option<u64> {
     let mut disk_map = parse_input(input);
 
     let mut files_try_moved: HashSet<u64> = HashSet::new();
-    let mut free_spaces = get_all_free_spaces(&disk_map);
+    let mut free_space: RangeSet<usize> = disk_map
+        .iter()
+        .enumerate()
+        .chunk_by(|(_, block)| block.is_none())
+        .into_iter()
+        .filter(|(free, _)| *free)
+        .filter_map(|(_, mut blocks)| {
+            let start = blocks.next()?.0;
+            Some(start..start + 1 + blocks.count())
+        })
+        .collect();
+    // free space left of these positions is too small for files of each size. The space a file
+    // frees lies right of all files still to move, so it is never searched and not tracked.
+    let mut first_fit = [0usize; 10];
 
     let mut file = None;
     loop {
@@ -74,34 +87,20 @@ pub fn part_two(input: &str) -> Option<u64> {
                     continue;
                 }
 
-                let filesize = file.1 - file.0;
-
-                if let Some((free_space_size, free_space_start)) = free_spaces
-                    .iter()
-                    .flat_map(|(size, heap)| heap.peek().map(|s| (*size, s.0)))
-                    .filter(|(size, start)| *size >= filesize && *start < file.0)
-                    .sorted_by(|a, b| a.1.cmp(&b.1))
-                    .next()
-                {
-                    for i in 0..=filesize {
-                        disk_map[free_space_start + i] = disk_map[file.0 + i];
-                        disk_map[file.0 + i] = None;
-                    }
+                let filesize = file.1 - file.0 + 1;
+
+                // leftmost free space that fits the file
+                let fitting = free_space
+                    .overlapping(&(first_fit[filesize].min(file.0)..file.0))
+                    .find(|free| free.len() >= filesize);
+                first_fit[filesize] = fitting.as_ref().map_or(file.0, |free| free.start);
 
-                    // Remove the one we chose
-                    free_spaces.get_mut(&free_space_size).unwrap().pop();
-
-                    if free_space_size > filesize {
-                        let new_size = free_space_size - filesize - 1;
-                        if let Some(h) = free_spaces.get_mut(&new_size) {
-                            h.push(Reverse(free_space_start + filesize + 1));
-                        } else {
-                            free_spaces.insert(
-                                new_size,
-                                BinaryHeap::from([Reverse(free_space_start + filesize + 1)]),
-                            );
-                        }
+                if let Some(free) = fitting {
+                    for i in 0..filesize {
+                        disk_map.swap(free.start + i, file.0 + i);
                     }
+
+                    free_space.remove(free.start..free.start + filesize);
                 }
             }
         }
@@ -137,53 +136,6 @@ fn find_next_file(disk_map: &Vec<Option<u64>>, start: usize) -> Option<(usize, u
     Some((l + 1, r))
 }
 
-fn get_all_free_spaces(disk_map: &Vec<Option<u64>>) -> HashMap<usize, BinaryHeap<Reverse<usize>>> {
-    let mut free_spaces: HashMap<usize, BinaryHeap<Reverse<usize>>> = HashMap::new();
-
-    let find_next_free_space = |start: usize| -> Option<(usize, usize)> {
-        let mut l = start;
-        loop {
-            match disk_map.get(l) {
-                None => return None,
-                Some(Some(_)) => {
-                    l += 1;
-                }
-                Some(None) => break,
-            }
-        }
-        let mut r = l;
-        loop {
-            match disk_map.get(r) {
-                None => break,
-                Some(Some(_)) => break,
-                Some(None) => {
-                    r += 1;
-                }
-            }
-        }
-        Some((l, r - 1))
-    };
-
-    let mut free_space = None;
-    loop {
-        free_space = find_next_free_space(free_space.map_or(0, |(_, r)| r + 1));
-        match free_space {
-            None => break,
-            Some(free_space) => {
-                let size = free_space.1 - free_space.0;
-                if let Some(h) = free_spaces.get_mut(&size) {
-                    h.push(Reverse(free_space.0));
-                } else {
-                    free_spaces.insert(size, BinaryHeap::from([Reverse(free_space.0)]));
-                }
-            }
-        }
-    }
-
-    free_spaces
-}
-
-
 #[cfg(test)]
 mod tests {
     use super::*;
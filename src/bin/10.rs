@@ -1,56 +1,29 @@
+use advent_of_code::Grid;
 use petgraph::prelude::*;
 use rayon::prelude::*;
 
 advent_of_code::solution!(10);
 
-#[cfg(not(debug_assertions))]
-const GRID_SIZE: usize = 55;
-
-#[cfg(debug_assertions)]
-const GRID_SIZE: usize = 8;
-
-fn parse_grid_to_graph(input: &str) -> (Graph<(usize, usize, u8), u8>, Vec<NodeIndex>) {
-    // Split input once to avoid repeated allocations
-    let lines: Vec<&[u8]> = input
-        .lines()
-        .map(|line| line.as_bytes())
-        .collect();
-
-
+fn parse_grid_to_graph(grid: &Grid<u8, &[u8]>) -> (Graph<(usize, usize, u8), u8>, Vec<NodeIndex>) {
     let mut graph = Graph::new();
-    let mut node_map = [NodeIndex::new(0); GRID_SIZE * GRID_SIZE];
+    let mut node_map = Grid::filled(grid.width(), grid.height(), NodeIndex::new(0));
     let mut trail_heads = Vec::with_capacity(300);
 
-    for r in 0..GRID_SIZE {
-        for c in 0..GRID_SIZE {
-            let value = lines[r][c];
-            let node = graph.add_node((r, c, value));
-            if value == b'0' {
-                trail_heads.push(node);
-            }
-            node_map[r * GRID_SIZE + c] = node;
+    for ((c, r), &value) in grid.iter() {
+        let node = graph.add_node((r, c, value));
+        if value == b'0' {
+            trail_heads.push(node);
         }
+        node_map[(c, r)] = node;
     }
 
 
-    for r in 0..GRID_SIZE {
-        for c in 0..GRID_SIZE {
-            let current_value = lines[r][c];
-            let current_node = node_map[r * GRID_SIZE + c];
-
-            let directions = [
-                r.checked_sub(1).map(|nr| (nr, c)),
-                if r + 1 < GRID_SIZE { Some((r + 1, c)) } else { None },
-                c.checked_sub(1).map(|nc| (r, nc)),
-                if c + 1 < GRID_SIZE { Some((r, c + 1)) } else { None },
-            ];
+    for (pos, &current_value) in grid.iter() {
+        let current_node = node_map[pos];
 
-            for dir in directions.iter().filter_map(|d| *d) {
-                let (nr, nc) = dir;
-                if lines[nr][nc] == current_value + 1 {
-                    let neighbor_node = node_map[nr * GRID_SIZE + nc];
-                    graph.add_edge(current_node, neighbor_node, 1);
-                }
+        for neighbor in grid.neighbours(pos) {
+            if grid[neighbor] == current_value + 1 {
+                graph.add_edge(current_node, node_map[neighbor], 1);
             }
         }
     }
@@ -59,15 +32,17 @@ fn parse_grid_to_graph(input: &str) -> (Graph<(usize, usize, u8), u8>, Vec<NodeI
 
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let (graph, trail_heads) = parse_grid_to_graph(input);
+    let grid = Grid::from_bytes(input);
+    let grid_size = grid.width() * grid.height();
+    let (graph, trail_heads) = parse_grid_to_graph(&grid);
 
     Some(
         trail_heads
             .par_iter()
             .map(|&start_node| {
 
-                let mut visited = vec![false; GRID_SIZE * GRID_SIZE];
-                let mut stack = Vec::with_capacity(GRID_SIZE * GRID_SIZE);
+                let mut visited = vec![false; grid_size];
+                let mut stack = Vec::with_capacity(grid_size);
 
                 stack.push(start_node);
                 visited[start_node.index()] = true;
@@ -96,12 +71,14 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let (graph, trail_heads) = parse_grid_to_graph(input);
+    let grid = Grid::from_bytes(input);
+    let grid_size = grid.width() * grid.height();
+    let (graph, trail_heads) = parse_grid_to_graph(&grid);
 
     let sum = trail_heads
         .par_iter()
         .map(|&start_node| {
-            let mut stack = vec![(start_node, vec![false; GRID_SIZE * GRID_SIZE])];
+            let mut stack = vec![(start_node, vec![false; grid_size])];
             let mut unique_paths = 0;
 
             while let Some((node, mut visited)) = stack.pop() {
@@ -1,24 +1,12 @@
-#![feature(maybe_uninit_uninit_array)]
-
-#[cfg(test)]
-const GRID_HEIGHT: i32 = 7;
-#[cfg(test)]
-const GRID_WIDTH: i32 = 11;
-#[cfg(test)]
-const MAX_ROBOTS: usize = 12;
-
-#[cfg(not(test))]
-const GRID_HEIGHT: i32 = 103;
-#[cfg(not(test))]
-const GRID_WIDTH: i32 = 101;
-#[cfg(not(test))]
-const MAX_ROBOTS: usize = 500;
-
-use std::mem::MaybeUninit;
-use std::ptr;
+use advent_of_code::template::Params;
 
 advent_of_code::solution!(14);
 
+/// Width and height of the grid and the number of robots.
+fn layout(params: Params) -> (i32, i32, usize) {
+    params.pick((11, 7, 12), (101, 103, 500))
+}
+
 #[derive(Debug, Clone)]
 struct Bound {
     min_x: i32,
@@ -74,32 +62,34 @@ impl Default for Robot {
     }
 }
 
-const QUADRANTS: [Bound; 4] = [
-    Bound {
-        min_x: 0,
-        min_y: 0,
-        max_x: GRID_WIDTH / 2,
-        max_y: GRID_HEIGHT / 2,
-    },
-    Bound {
-        min_x: GRID_WIDTH / 2 + 1,
-        min_y: 0,
-        max_x: GRID_WIDTH,
-        max_y: GRID_HEIGHT / 2,
-    },
-    Bound {
-        min_x: 0,
-        min_y: GRID_HEIGHT / 2 + 1,
-        max_x: GRID_WIDTH / 2,
-        max_y: GRID_HEIGHT,
-    },
-    Bound {
-        min_x: GRID_WIDTH / 2 + 1,
-        min_y: GRID_HEIGHT / 2 + 1,
-        max_x: GRID_WIDTH,
-        max_y: GRID_HEIGHT,
-    },
-];
+fn quadrants(width: i32, height: i32) -> [Bound; 4] {
+    [
+        Bound {
+            min_x: 0,
+            min_y: 0,
+            max_x: width / 2,
+            max_y: height / 2,
+        },
+        Bound {
+            min_x: width / 2 + 1,
+            min_y: 0,
+            max_x: width,
+            max_y: height / 2,
+        },
+        Bound {
+            min_x: 0,
+            min_y: height / 2 + 1,
+            max_x: width / 2,
+            max_y: height,
+        },
+        Bound {
+            min_x: width / 2 + 1,
+            min_y: height / 2 + 1,
+            max_x: width,
+            max_y: height,
+        },
+    ]
+}
 
 pub fn get_x_y(part: &str) -> (i32, i32) {
     // Split once by the comma, then parse each part (x, y) directly
@@ -110,27 +100,29 @@ pub fn get_x_y(part: &str) -> (i32, i32) {
     (x, y)
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str, params: Params) -> Option<u32> {
+    let (width, height, max_robots) = layout(params);
+    let bounds = quadrants(width, height);
     let mut quadrants = [0; 4];
 
     let bytes = input.as_bytes();
 
-    let mut i = 2;
+    let mut scanner = Scanner::new(bytes);
 
     let mut robot_count = 0;
-    while robot_count < MAX_ROBOTS {
-        let robot = Robot::wait_secs(&parse_bot(bytes, &mut i), 100, GRID_WIDTH, GRID_HEIGHT);
+    while robot_count < max_robots {
+        let robot = Robot::wait_secs(&parse_bot(&mut scanner), 100, width, height);
 
-        if QUADRANTS[0].contains(&robot) {
+        if bounds[0].contains(&robot) {
             quadrants[0] += 1;
         }
-        if QUADRANTS[1].contains(&robot) {
+        if bounds[1].contains(&robot) {
             quadrants[1] += 1;
         }
-        if QUADRANTS[2].contains(&robot) {
+        if bounds[2].contains(&robot) {
             quadrants[2] += 1;
         }
-        if QUADRANTS[3].contains(&robot) {
+        if bounds[3].contains(&robot) {
             quadrants[3] += 1;
         }
         robot_count += 1;
@@ -140,16 +132,13 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 use rayon::prelude::*;
-use advent_of_code::parse_i32;
-
-fn parse_bot(bytes: &[u8], i: &mut usize) -> Robot {
-    let pos_x = parse_i32(&bytes, i);
-    let pos_y = parse_i32(&bytes, i);
+use advent_of_code::{BitGrid, Scanner};
 
-    *i += 2;
-    let vel_x = parse_i32(&bytes, i);
-    let vel_y = parse_i32(&bytes, i);
-    *i += 2;
+fn parse_bot(scanner: &mut Scanner) -> Robot {
+    let pos_x = scanner.next_int().unwrap();
+    let pos_y = scanner.next_int().unwrap();
+    let vel_x = scanner.next_int().unwrap();
+    let vel_y = scanner.next_int().unwrap();
 
     Robot {
         pos: (pos_x, pos_y),
@@ -159,38 +148,31 @@ fn parse_bot(bytes: &[u8], i: &mut usize) -> Robot {
 
 
 
-pub fn part_two(input: &str) -> Option<i32> {
-    #[allow(invalid_value)]
-    let mut robots: [Robot; MAX_ROBOTS] = unsafe { MaybeUninit::uninit().assume_init() }; // Uninitialized array
-    let mut robot_count = 0;
+pub fn part_two(input: &str, params: Params) -> Option<i32> {
+    // the robots of the example never form a Christmas tree.
+    if params.is_example() {
+        return None;
+    }
 
-    let bytes = input.as_bytes();
+    let (width, height, max_robots) = layout(params);
+    let mut robots = Vec::with_capacity(max_robots);
 
-    let mut i = 2;
-    while robot_count < MAX_ROBOTS {
-        let robot = parse_bot(bytes, &mut i);
+    let bytes = input.as_bytes();
 
-        unsafe {
-            ptr::write(&mut robots[robot_count], robot);
-        }
-        robot_count += 1;
+    let mut scanner = Scanner::new(bytes);
+    while robots.len() < max_robots {
+        robots.push(parse_bot(&mut scanner));
     }
 
     (0..5000).into_par_iter().find_map_first(|i| {
-        let mut seen_positions = [0u128; GRID_WIDTH as usize];
+        let mut seen_positions = BitGrid::new(width as usize, height as usize);
         let mut found = true;
 
         for robot in &robots {
-            unsafe {
-                let (x, y) = robot.wait_secs(i * 2, GRID_WIDTH, GRID_HEIGHT).pos;
-                let seen_bits_ptr = seen_positions.as_mut_ptr().add(x as usize);
-                let bitmask = 1 << y as usize;
-                if (*seen_bits_ptr & bitmask) != 0 {
-                    found = false;
-                    break;
-                }
-
-                *seen_bits_ptr |= bitmask
+            let (x, y) = robot.wait_secs(i * 2, width, height).pos;
+            if !seen_positions.insert((x as usize, y as usize)) {
+                found = false;
+                break;
             }
         }
 
@@ -208,13 +190,19 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", DAY),
+            Params::Example,
+        );
         assert_eq!(result, Some(12));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(
+            &advent_of_code::template::read_file("examples", DAY),
+            Params::Example,
+        );
         assert_eq!(result, None);
     }
 }
@@ -1,23 +1,18 @@
-use advent_of_code::convert_offset;
+use advent_of_code::{convert_offset, Direction};
+use advent_of_code::template::visualize::{self, Frame};
 
 advent_of_code::solution!(15);
 
-macro_rules! generate_translations {
-    () => {{
-        const fn generate() -> [(i32, i32); 255] {
-            let mut m = [(0, 0); 255];
-            m[b'^' as usize] = (0, -1);
-            m[b'v' as usize] = (0, 1);
-            m[b'<' as usize] = (-1, 0);
-            m[b'>' as usize] = (1, 0);
-            m
-        }
-        generate()
-    }};
+/// Width of the square grid, i.e. the length of its first line.
+fn grid_size(input: &str) -> i32 {
+    input.find('\n').unwrap_or(input.len()) as i32
 }
 
-
-const TRANSLATIONS: [(i32, i32); 255] = generate_translations!();
+/// Position of the robot `@` as `(x, y)`.
+fn robot_position(input: &str, grid_size: i32) -> (i32, i32) {
+    let offset = input.find('@').unwrap_or(0) as i32;
+    (offset % (grid_size + 1), offset / (grid_size + 1))
+}
 
 fn score_grid(bytes: &[u8], grid_size: i32) -> u32 {
     let mut sum = 0;
@@ -34,11 +29,7 @@ fn score_grid(bytes: &[u8], grid_size: i32) -> u32 {
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    #[cfg(test)]
-    const GRID_SIZE: i32 = 10;
-
-    #[cfg(not(test))]
-    const GRID_SIZE: i32 = 50;
+    let grid_size = grid_size(input);
 
     let mut bytes = input.as_bytes().to_owned();
 
@@ -46,17 +37,14 @@ pub fn part_one(input: &str) -> Option<u32> {
         std::slice::from_raw_parts(
             input
                 .as_ptr()
-                .add((GRID_SIZE * GRID_SIZE + GRID_SIZE + 1) as usize),
-            input.len() - (GRID_SIZE * GRID_SIZE + GRID_SIZE + 1) as usize,
+                .add((grid_size * grid_size + grid_size + 1) as usize),
+            input.len() - (grid_size * grid_size + grid_size + 1) as usize,
         )
     };
 
-    let mut current_pos: (i32, i32) = (
-        GRID_SIZE as i32 / 2 - 1,
-        GRID_SIZE as i32 / 2 - 1, // 2,2
-    );
+    let mut current_pos = robot_position(input, grid_size);
 
-    bytes[convert_offset(current_pos.0, current_pos.1, GRID_SIZE)] = b'.';
+    bytes[convert_offset(current_pos.0, current_pos.1, grid_size)] = b'.';
 
     let mut moves_idx = 0;
     while moves_idx < moves.len() {
@@ -66,26 +54,43 @@ pub fn part_one(input: &str) -> Option<u32> {
             continue;
         }
 
-        let t = TRANSLATIONS[m as usize];
+        let t = Direction::try_from(m).unwrap().delta();
 
         moves_idx += 1;
 
-        if can_move(&mut bytes, current_pos, t, GRID_SIZE, GRID_SIZE) {
-            do_move(&mut bytes, current_pos, t, GRID_SIZE, GRID_SIZE);
+        if can_move(&mut bytes, current_pos, t, grid_size, grid_size) {
+            do_move(&mut bytes, current_pos, t, grid_size, grid_size);
             current_pos = (current_pos.0 + t.0, current_pos.1 + t.1);
         } else {
             if moves[moves_idx] == m {
                 moves_idx += 1;
             }
         }
+
+        visualize::emit_every(FRAME_EVERY, || {
+            warehouse_frame(&bytes, current_pos, grid_size, grid_size)
+        });
     }
+    visualize::emit(|| warehouse_frame(&bytes, current_pos, grid_size, grid_size));
 
-    Some(score_grid(&bytes, GRID_SIZE))
+    Some(score_grid(&bytes, grid_size))
 }
 
-pub fn transform_input(input: &str) -> (Vec<u8>, &str) {
+/// Only every 50th move is drawn, which keeps the 20,000 moves of an input to about 20 seconds.
+const FRAME_EVERY: usize = 50;
+
+/// The warehouse with the robot at `robot`, see `cargo solve 15 --visualize`.
+fn warehouse_frame(bytes: &[u8], robot: (i32, i32), grid_width: i32, grid_height: i32) -> Frame {
+    let mut frame = Frame::from_fn(grid_width as usize, grid_height as usize, |x, y| {
+        bytes[convert_offset(x as i32, y as i32, grid_width)]
+    });
+    frame.set(robot.0 as usize, robot.1 as usize, b'@');
+    frame
+}
+
+pub fn transform_input(input: &str, grid_size: i32) -> (Vec<u8>, &str) {
     let mut bytes = Vec::with_capacity(input.len() * 2);
-    let grid_len = 50 * 50 + 50;
+    let grid_len = (grid_size * grid_size + grid_size) as usize;
 
     for (idx, ch) in input.chars().enumerate() {
         if idx >= grid_len {
@@ -104,20 +109,16 @@ pub fn transform_input(input: &str) -> (Vec<u8>, &str) {
 }
 
 pub fn part_two(input: &str) -> Option<i32> {
-    #[cfg(test)]
-    const GRID_HEIGHT: i32 = 10;
-
-    #[cfg(not(test))]
-    const GRID_HEIGHT: i32 = 50;
-    const GRID_WIDTH: i32 = GRID_HEIGHT * 2;
+    let grid_height = grid_size(input);
+    let grid_width = grid_height * 2;
 
     // transform the input
-    let (bytes, moves) = &mut transform_input(input);
+    let (bytes, moves) = &mut transform_input(input, grid_height);
     let moves = moves.as_bytes();
 
-    // initial position of the robot. it is slightly offset from the center due to the transformation
-    // this moves it 1 to the left. The indexing is also done from 0 so we always need to subtract one
-    let mut current_pos = (GRID_WIDTH / 2 - 2, GRID_HEIGHT / 2 - 1);
+    // the robot starts on the left half of its widened cell.
+    let (x, y) = robot_position(input, grid_height);
+    let mut current_pos = (2 * x, y);
 
     // the moves start after the grid.
     let mut moves_idx = 0;
@@ -131,24 +132,29 @@ pub fn part_two(input: &str) -> Option<i32> {
             continue;
         }
 
-        let direction = TRANSLATIONS[m as usize];
-        let will_move = can_move(bytes, current_pos, direction, GRID_WIDTH, GRID_HEIGHT);
+        let direction = Direction::try_from(m).unwrap().delta();
+        let will_move = can_move(bytes, current_pos, direction, grid_width, grid_height);
 
         if will_move {
-            do_move(bytes, current_pos, direction, GRID_WIDTH, GRID_HEIGHT);
+            do_move(bytes, current_pos, direction, grid_width, grid_height);
             current_pos = (current_pos.0 + direction.0, current_pos.1 + direction.1);
         } else {
             if moves[moves_idx] == m {
                 moves_idx += 1;
             }
         }
+
+        visualize::emit_every(FRAME_EVERY, || {
+            warehouse_frame(bytes, current_pos, grid_width, grid_height)
+        });
     }
+    visualize::emit(|| warehouse_frame(bytes, current_pos, grid_width, grid_height));
 
     // get the postition of the left most part of the crate
     let mut crate_postions = Vec::new();
-    for x in 0..GRID_WIDTH {
-        for y in 0..GRID_HEIGHT {
-            let c = bytes[convert_offset(x, y, GRID_WIDTH)];
+    for x in 0..grid_width {
+        for y in 0..grid_height {
+            let c = bytes[convert_offset(x, y, grid_width)];
             if c == b'[' {
                 crate_postions.push((x, y));
             }
@@ -252,6 +258,6 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(9021));
     }
 }
@@ -1,101 +1,45 @@
-use std::collections::{HashMap, VecDeque};
-use itertools::Itertools;
-use rustc_hash::FxHashSet;
+use advent_of_code::search::{astar, Search};
+use advent_of_code::{Direction, Grid, Point};
 
 advent_of_code::solution!(16);
 
 type Coord = (usize, usize);
-type PredMap = HashMap<(Coord, usize), Vec<(Coord, usize)>>;
-
-static MOVE_TOWARD: [fn(Coord) -> Coord; 4] = [
-    |(x, y)| (x + 1, y),
-    |(x, y)| (x, y - 1),
-    |(x, y)| (x - 1, y),
-    |(x, y)| (x, y + 1),
-];
-
-
-fn move_or_rotate(curr_pos: Coord, curr_dir: usize, rot: usize) -> (Coord, usize) {
-    let next_dir = (curr_dir + rot) % 4;
-    let next_pos = if rot == 0 {
-        MOVE_TOWARD[next_dir](curr_pos)
-    } else {
-        curr_pos
-    };
-    (next_pos, next_dir)
-}
-
-fn find_path(map: &[Vec<char>]) -> Option<(Coord, usize, isize, PredMap)> {
-    #[cfg(test)]
-    let (start, goal) = (
-        (15, 1), (1,15)
+type State = (Coord, Direction);
+
+/// Searches all cheapest paths from `S` to `E`. Moving costs 1 and turning costs 1000.
+fn find_path(input: &str) -> Option<Search<State, usize>> {
+    let map = Grid::from_bytes(input);
+    let start = map.find(&b'S')?;
+    let goal = map.find(&b'E')?;
+
+    let search = astar(
+        (start, Direction::East),
+        |&(curr_pos, curr_dir)| {
+            let forward = Point::from(curr_pos)
+                .checked_add_direction(curr_dir)
+                .map(Coord::from)
+                .filter(|&next_pos| map[next_pos] != b'#')
+                .map(|next_pos| ((next_pos, curr_dir), 1));
+
+            forward.into_iter().chain([
+                ((curr_pos, curr_dir.rotate_clockwise()), 1000),
+                ((curr_pos, curr_dir.rotate_counterclockwise()), 1000),
+            ])
+        },
+        |&(pos, _)| Point::from(pos).manhattan(Point::from(goal)),
+        |&(pos, _)| pos == goal,
     );
 
-    #[cfg(not(test))]
-    let (start, goal) = (
-        (139, 1), (1,139)
-    );
-
-    let (init_dir, init_score) = (0usize, 0isize);
-    let mut stack = VecDeque::from([(start, init_dir, init_score)]);
-    let mut path_node = HashMap::from([((start, init_dir), init_score)]);
-    let mut preds = PredMap::new();
-
-    while let Some((curr_pos, curr_dir, score)) = stack.pop_front() {
-        for (rot, cost) in [(3, -1000), (0, -1), (1, -1000)] {
-            let next_node @ (next_pos, next_dir) = move_or_rotate(curr_pos, curr_dir, rot);
-            let new_score = score + cost;
-            let old_score = path_node.get(&(next_pos, next_dir)).copied();
-
-            if map[next_pos.1][next_pos.0] != '#' && Some(new_score) >= old_score {
-                if Some(new_score) > old_score && curr_pos != goal {
-                    stack.push_back((next_pos, next_dir, new_score));
-                }
-
-                path_node.remove(&next_node);
-                path_node.insert(next_node, new_score);
-
-                // Update next node predecessors
-                let mut new_preds = vec![(curr_pos, curr_dir)];
-                let olds = preds.remove(&next_node).unwrap_or_default().into_iter();
-                let keep_old_preds = old_score == Some(new_score);
-                new_preds.extend(olds.take_while(|_| keep_old_preds));
-                preds.insert(next_node, new_preds);
-            }
-        }
-    }
-    let (direction_arrival, score) = (0..4)
-        .filter_map(|dir| Some((dir, path_node.get(&(goal, dir))?)))
-        .max_by_key(|(_dir, score)| **score)?;
-    Some((goal, direction_arrival, -*score, preds))
-}
-
-fn parse_input(input: &str) -> Vec<Vec<char>> {
-    input
-        .split("\n")
-        .map(|line| line.chars().collect())
-        .collect()
+    Some(search)
 }
 
-fn part_one(input: &str) -> Option<isize> {
-    let map = parse_input(input);
-    Some(find_path(&map)?.2)
+fn part_one(input: &str) -> Option<usize> {
+    find_path(input)?.goal_cost()
 }
 
 fn part_two(input: &str) -> Option<usize> {
-    let map = parse_input(input);
-    let (goal, arrival_dir, _, preds) = find_path(&map)?;
-
-    let mut visited = FxHashSet::default();
-    let mut stack_back = VecDeque::from([(goal, arrival_dir)]);
-    while let Some((pos, curr_dir)) = stack_back.pop_front() {
-        match (visited.insert((pos, curr_dir)), preds.get(&(pos, curr_dir))) {
-            (true, Some(preds)) => stack_back.extend(preds),
-            _ => (),
-        }
-    }
-
-    Some(visited.into_iter().counts_by(|(coord, _dir)| coord).len())
+    let search = find_path(input)?;
+    Some(search.tiles(search.goals().to_vec(), |&(pos, _dir)| pos).len())
 }
 #[cfg(test)]
 mod tests {
@@ -3,7 +3,7 @@
 use std::ops::Index;
 use std::simd::prelude::*;
 use lazy_static::lazy_static;
-use advent_of_code::parse_unsigned;
+use advent_of_code::Scanner;
 
 advent_of_code::solution!(17);
 
@@ -18,16 +18,15 @@ struct Computer {
 
 impl Computer {
     fn parse_from_bytes(bytes: &[u8]) -> Self {
-        let mut i = 12;
-        let register_a = parse_unsigned(bytes, &mut i);
-        i += 38;
+        let mut scanner = Scanner::new(bytes);
+        let register_a = scanner.next_uint().unwrap();
+        scanner.skip_until(b"Program: ").unwrap();
 
         let mut program = [0; 16];
         let mut program_len = 0;
-        while i < bytes.len() && program_len < 16 {
-            program[program_len] = bytes[i] - b'0';
+        for operation in scanner.uints().take(16) {
+            program[program_len] = operation;
             program_len += 1;
-            i += 2;
         }
 
         Computer {
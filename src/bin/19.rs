@@ -1,39 +1,36 @@
 advent_of_code::solution!(19);
 
-use dashmap::DashMap;
+use advent_of_code::memo::{self, Memo};
 use rayon::prelude::*;
 use rustc_hash::{FxHashSet};
 
 fn solve_design<'a>(
     design: &'a str,
     towels: &FxHashSet<String>,
-    memo: &DashMap<&'a str, usize>,
+    memo: &Memo<&'a str, usize>,
 ) -> usize {
     if design.is_empty() {
         return 1;
     }
 
-    if let Some(cached_result) = memo.get(&design) {
-        return *cached_result;
-    }
-
-    let mut possibilities = 0;
-    for towel in towels {
-        if &design == towel {
-            possibilities += 1;
-            continue;
-        }
+    memo.get_or_insert_with(design, || {
+        let mut possibilities = 0;
+        for towel in towels {
+            if &design == towel {
+                possibilities += 1;
+                continue;
+            }
 
-        if design.starts_with(towel) {
-            let result = solve_design(&design[towel.len()..], towels, memo);
-            if result != 0 {
-                possibilities += result;
+            if design.starts_with(towel) {
+                let result = solve_design(&design[towel.len()..], towels, memo);
+                if result != 0 {
+                    possibilities += result;
+                }
             }
         }
-    }
 
-    memo.insert(&design, possibilities);
-    possibilities
+        possibilities
+    })
 }
 
 fn parse_input(input: &str) -> (FxHashSet<String>, Vec<String>) {
@@ -59,33 +56,35 @@ fn parse_input(input: &str) -> (FxHashSet<String>, Vec<String>) {
 
 pub fn part_one(input: &str) -> Option<usize> {
     let (towels, designs) = parse_input(input);
-    let memo = DashMap::new();
-
-    Some(
-        designs
-            .par_iter()
-            .filter(|&design| solve_design(design, &towels, &memo) != 0)
-            .count(),
-    )
+
+    memo::sharded(|memo| {
+        Some(
+            designs
+                .par_iter()
+                .filter(|&design| solve_design(design, &towels, memo) != 0)
+                .count(),
+        )
+    })
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
     let (towels, designs) = parse_input(input);
-    let memo = DashMap::new();
-
-    Some(
-        designs
-            .par_iter()
-            .filter_map(|design| {
-                let count = solve_design(design, &towels, &memo);
-                if count != 0 {
-                    Some(count)
-                } else {
-                    None
-                }
-            })
-            .sum(),
-    )
+
+    memo::sharded(|memo| {
+        Some(
+            designs
+                .par_iter()
+                .filter_map(|design| {
+                    let count = solve_design(design, &towels, memo);
+                    if count != 0 {
+                        Some(count)
+                    } else {
+                        None
+                    }
+                })
+                .sum(),
+        )
+    })
 }
 
 #[cfg(test)]
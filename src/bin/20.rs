@@ -1,34 +1,21 @@
+use advent_of_code::search;
 use rayon::prelude::*;
 use rustc_hash::{FxHashMap, FxHashSet};
-use std::collections::VecDeque;
 
 advent_of_code::solution!(20);
 
-fn explore(start: (i32, i32), free_spaces: &FxHashSet<(i32, i32)>) -> FxHashMap<(i32, i32), i32> {
-    let mut distances = FxHashMap::with_capacity_and_hasher(free_spaces.len(), Default::default()); // Pre-allocate for fewer resizes
-    let mut queue = VecDeque::with_capacity(free_spaces.len()); // Pre-allocate for fewer resizes
-
-    distances.insert(start, 0);
-    queue.push_back(start);
-
-    while let Some((cx, cy)) = queue.pop_front() {
-        let current_distance = distances[&(cx, cy)];
-
-        for (dx, dy) in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
-            let np = (cx + dx, cy + dy);
-
-            // Only process unvisited and valid free spaces
-            if free_spaces.contains(&np) && !distances.contains_key(&np) {
-                distances.insert(np, current_distance + 1);
-                queue.push_back(np);
-            }
-        }
-    }
-
-    distances
+fn explore(start: (i32, i32), free_spaces: &FxHashSet<(i32, i32)>) -> FxHashMap<(i32, i32), usize> {
+    search::bfs(start, |&(cx, cy)| {
+        // Only process valid free spaces
+        [(0, 1), (0, -1), (1, 0), (-1, 0)]
+            .map(|(dx, dy)| (cx + dx, cy + dy))
+            .into_iter()
+            .filter(|np| free_spaces.contains(np))
+    })
+    .into_costs()
 }
 
-fn get_savings(distances: &FxHashMap<(i32, i32), i32>, jump_size: i32) -> usize {
+fn get_savings(distances: &FxHashMap<(i32, i32), usize>, jump_size: i32) -> usize {
     // Pre-calculate jump range once, with capacity hint
     let capacity = ((2 * jump_size + 1) * (2 * jump_size + 1)) as usize;
     let jump_range = {
@@ -54,8 +41,8 @@ fn get_savings(distances: &FxHashMap<(i32, i32), i32>, jump_size: i32) -> usize
 
                 // Avoid option handling with direct get
                 if let Some(&np_cost) = distances.get(&np) {
-                    let cheat_cost = dx.abs() + dy.abs();
-                    count += u32::from(initial_cost - np_cost - cheat_cost >= 100);
+                    let cheat_cost = (dx.abs() + dy.abs()) as usize;
+                    count += u32::from(initial_cost >= np_cost + cheat_cost + 100);
                 }
             }
 
@@ -1,6 +1,4 @@
-use rustc_hash::FxHashMap;
-
-use memoize::memoize;
+use advent_of_code::memo::{self, Memo, PerThread};
 use rayon::prelude::*;
 
 advent_of_code::solution!(21);
@@ -30,55 +28,59 @@ const ARROWPAD: [(i32, i32); 255] = {
     a
 };
 
-#[memoize(CustomHasher: FxHashMap, HasherInit: FxHashMap::default())]
-fn press_arrows(i: i32, j: i32, steps: usize, h_first: bool) -> usize {
+/// Each rayon thread keeps its own results, as they are cheap and looked up very often.
+type ArrowMemo = Memo<(i32, i32, usize, bool), usize, PerThread<(i32, i32, usize, bool), usize>>;
+
+fn press_arrows(i: i32, j: i32, steps: usize, h_first: bool, memo: &ArrowMemo) -> usize {
     if steps == 0 {
         return (i.unsigned_abs() + j.unsigned_abs()) as usize + 1;
     }
 
-    let (ii, jj) = (i.unsigned_abs() as usize, j.unsigned_abs() as usize);
-    let mut chunk = Vec::with_capacity(ii + jj + 1);
-
-    if h_first {
-        chunk.extend(vec![if j > 0 { '<' } else { '>' }; jj]);
-        chunk.extend(vec![if i > 0 { '^' } else { 'v' }; ii]);
-    } else {
-        chunk.extend(vec![if i > 0 { '^' } else { 'v' }; ii]);
-        chunk.extend(vec![if j > 0 { '<' } else { '>' }; jj]);
-    }
-
-    chunk.push('A');
-
-    let mut loc = ARROWPAD[b'A' as usize];
-
-    chunk
-        .into_iter()
-        .map(|c| {
-            let n = ARROWPAD[c as u8 as usize];
-            let p = loc;
-            loc = n;
-            let d = (p.0 - n.0, p.1 - n.1);
-            if d.0 == 0 || d.1 == 0 {
-                // straight line, search only once, order is irrelevant
-                press_arrows(d.0, d.1, steps - 1, false)
-            } else if n == (1, 0) && p.0 == 0 {
-                // must search down first
-                press_arrows(d.0, d.1, steps - 1, false)
-            } else if p == (1, 0) && n.0 == 0 {
-                // must search horiz first
-                press_arrows(d.0, d.1, steps - 1, true)
-            } else {
-                // can search in either order
-                std::cmp::min(
-                    press_arrows(d.0, d.1, steps - 1, false),
-                    press_arrows(d.0, d.1, steps - 1, true),
-                )
-            }
-        })
-        .sum()
+    memo.get_or_insert_with((i, j, steps, h_first), || {
+        let (ii, jj) = (i.unsigned_abs() as usize, j.unsigned_abs() as usize);
+        let mut chunk = Vec::with_capacity(ii + jj + 1);
+
+        if h_first {
+            chunk.extend(vec![if j > 0 { '<' } else { '>' }; jj]);
+            chunk.extend(vec![if i > 0 { '^' } else { 'v' }; ii]);
+        } else {
+            chunk.extend(vec![if i > 0 { '^' } else { 'v' }; ii]);
+            chunk.extend(vec![if j > 0 { '<' } else { '>' }; jj]);
+        }
+
+        chunk.push('A');
+
+        let mut loc = ARROWPAD[b'A' as usize];
+
+        chunk
+            .into_iter()
+            .map(|c| {
+                let n = ARROWPAD[c as u8 as usize];
+                let p = loc;
+                loc = n;
+                let d = (p.0 - n.0, p.1 - n.1);
+                if d.0 == 0 || d.1 == 0 {
+                    // straight line, search only once, order is irrelevant
+                    press_arrows(d.0, d.1, steps - 1, false, memo)
+                } else if n == (1, 0) && p.0 == 0 {
+                    // must search down first
+                    press_arrows(d.0, d.1, steps - 1, false, memo)
+                } else if p == (1, 0) && n.0 == 0 {
+                    // must search horiz first
+                    press_arrows(d.0, d.1, steps - 1, true, memo)
+                } else {
+                    // can search in either order
+                    std::cmp::min(
+                        press_arrows(d.0, d.1, steps - 1, false, memo),
+                        press_arrows(d.0, d.1, steps - 1, true, memo),
+                    )
+                }
+            })
+            .sum()
+    })
 }
 
-fn press_buttons(sequence: &str, steps: usize) -> usize {
+fn press_buttons(sequence: &str, steps: usize, memo: &ArrowMemo) -> usize {
     let mut loc = NUMPAD[b'A' as usize];
 
     sequence[0..3].parse::<usize>().unwrap()
@@ -93,15 +95,15 @@ fn press_buttons(sequence: &str, steps: usize) -> usize {
             loc = n;
             if p.0 == 3 && n.1 == 0 {
                 // must move up first
-                press_arrows(d.0, d.1, steps, false)
+                press_arrows(d.0, d.1, steps, false, memo)
             } else if p.1 == 0 && n.0 == 3 {
                 // must move right first
-                press_arrows(d.0, d.1, steps, true)
+                press_arrows(d.0, d.1, steps, true, memo)
             } else {
                 // move in either direction
                 std::cmp::min(
-                    press_arrows(d.0, d.1, steps, true),
-                    press_arrows(d.0, d.1, steps, false),
+                    press_arrows(d.0, d.1, steps, true, memo),
+                    press_arrows(d.0, d.1, steps, false, memo),
                 )
             }
         })
@@ -109,15 +111,11 @@ fn press_buttons(sequence: &str, steps: usize) -> usize {
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    memoized_flush_press_arrows();
-
-    Some(input.par_lines().map(|s| press_buttons(s, 2)).sum())
+    memo::per_thread(|memo| Some(input.par_lines().map(|s| press_buttons(s, 2, memo)).sum()))
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    memoized_flush_press_arrows();
-
-    Some(input.par_lines().map(|s| press_buttons(s, 25)).sum())
+    memo::per_thread(|memo| Some(input.par_lines().map(|s| press_buttons(s, 25, memo)).sum()))
 }
 
 #[cfg(test)]
@@ -0,0 +1,375 @@
+//! A grid of bits with any size, indexed by `(x, y)` like [`Grid`].
+
+use std::{
+    fmt::Display,
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign},
+};
+
+use crate::{Grid, Point};
+
+const BITS: usize = u64::BITS as usize;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// An empty grid, i.e. with no bit set.
+    pub fn new(width: usize, height: usize) -> Self {
+        let words_per_row = width.div_ceil(BITS);
+        Self {
+            width,
+            height,
+            words_per_row,
+            words: vec![0; words_per_row * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut((usize, usize)) -> bool) -> Self {
+        let mut grid = Self::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                if f((x, y)) {
+                    grid.insert((x, y));
+                }
+            }
+        }
+        grid
+    }
+
+    /// Sets the bits of all cells that match `f`, e.g. `|&c| c == b'#'` for the walls of a byte grid.
+    pub fn from_grid<T, C: AsRef<[T]>>(grid: &Grid<T, C>, mut f: impl FnMut(&T) -> bool) -> Self {
+        Self::from_fn(grid.width(), grid.height(), |pos| f(&grid[pos]))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns whether `pos` is inside the grid.
+    pub fn contains(&self, pos: impl Into<Point<usize>>) -> bool {
+        let pos = pos.into();
+        pos.x < self.width && pos.y < self.height
+    }
+
+    /// Returns the bit at `pos`, or `false` outside of the grid.
+    pub fn get(&self, pos: impl Into<Point<usize>>) -> bool {
+        let pos = pos.into();
+        self.contains(pos) && self.words[self.word(pos)] & bit(pos.x) != 0
+    }
+
+    /// Sets the bit at `pos` and returns whether it was unset before.
+    ///
+    /// # Panics
+    ///
+    /// If `pos` is outside of the grid.
+    pub fn insert(&mut self, pos: impl Into<Point<usize>>) -> bool {
+        let pos = self.checked(pos.into());
+        let index = self.word(pos);
+        let word = &mut self.words[index];
+        let unset = *word & bit(pos.x) == 0;
+        *word |= bit(pos.x);
+        unset
+    }
+
+    /// Unsets the bit at `pos` and returns whether it was set before.
+    ///
+    /// # Panics
+    ///
+    /// If `pos` is outside of the grid.
+    pub fn remove(&mut self, pos: impl Into<Point<usize>>) -> bool {
+        let pos = self.checked(pos.into());
+        let index = self.word(pos);
+        let word = &mut self.words[index];
+        let set = *word & bit(pos.x) != 0;
+        *word &= !bit(pos.x);
+        set
+    }
+
+    /// # Panics
+    ///
+    /// If `pos` is outside of the grid.
+    pub fn set(&mut self, pos: impl Into<Point<usize>>, value: bool) {
+        if value {
+            self.insert(pos);
+        } else {
+            self.remove(pos);
+        }
+    }
+
+    /// Flips the bit at `pos`.
+    ///
+    /// # Panics
+    ///
+    /// If `pos` is outside of the grid.
+    pub fn toggle(&mut self, pos: impl Into<Point<usize>>) {
+        let pos = self.checked(pos.into());
+        let word = self.word(pos);
+        self.words[word] ^= bit(pos.x);
+    }
+
+    /// The number of set bits.
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// The number of set bits in row `y`.
+    pub fn count_row(&self, y: usize) -> usize {
+        self.row(y).iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Unsets all bits.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// The packed words of row `y`, with `x = 0` in the lowest bit of the first word.
+    pub fn row(&self, y: usize) -> &[u64] {
+        &self.words[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    /// The positions of all set bits in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, &word)| {
+            let (y, first_x) = (i / self.words_per_row, (i % self.words_per_row) * BITS);
+            let mut word = word;
+            std::iter::from_fn(move || {
+                (word != 0).then(|| {
+                    let x = first_x + word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    (x, y)
+                })
+            })
+        })
+    }
+
+    /// Moves all bits `n` columns to the east. Bits that leave their row are dropped.
+    pub fn shift_east(&mut self, n: usize) {
+        let (words, bits) = (n / BITS, n % BITS);
+        for row in self.rows_mut() {
+            for i in (0..row.len()).rev() {
+                let high = i.checked_sub(words).map_or(0, |j| row[j] << bits);
+                let low = match i.checked_sub(words + 1) {
+                    Some(j) if bits > 0 => row[j] >> (BITS - bits),
+                    _ => 0,
+                };
+                row[i] = high | low;
+            }
+        }
+        self.mask_rows();
+    }
+
+    /// Moves all bits `n` columns to the west. Bits that leave their row are dropped.
+    pub fn shift_west(&mut self, n: usize) {
+        let (words, bits) = (n / BITS, n % BITS);
+        for row in self.rows_mut() {
+            for i in 0..row.len() {
+                let low = row.get(i + words).map_or(0, |&w| w >> bits);
+                let high = match row.get(i + words + 1) {
+                    Some(&w) if bits > 0 => w << (BITS - bits),
+                    _ => 0,
+                };
+                row[i] = low | high;
+            }
+        }
+    }
+
+    /// Moves all rows `n` rows to the south. Rows that leave the grid are dropped.
+    pub fn shift_south(&mut self, n: usize) {
+        let shift = (n * self.words_per_row).min(self.words.len());
+        self.words.rotate_right(shift);
+        self.words[..shift].fill(0);
+    }
+
+    /// Moves all rows `n` rows to the north. Rows that leave the grid are dropped.
+    pub fn shift_north(&mut self, n: usize) {
+        let shift = (n * self.words_per_row).min(self.words.len());
+        self.words.rotate_left(shift);
+        let len = self.words.len();
+        self.words[len - shift..].fill(0);
+    }
+
+    fn rows_mut(&mut self) -> impl Iterator<Item = &mut [u64]> {
+        // grids without columns have no words, but chunks must not be empty.
+        self.words.chunks_exact_mut(self.words_per_row.max(1))
+    }
+
+    fn word(&self, pos: Point<usize>) -> usize {
+        pos.y * self.words_per_row + pos.x / BITS
+    }
+
+    fn checked(&self, pos: Point<usize>) -> Point<usize> {
+        assert!(
+            self.contains(pos),
+            "({}, {}) is outside of the {}x{} grid",
+            pos.x,
+            pos.y,
+            self.width,
+            self.height
+        );
+        pos
+    }
+
+    /// Unsets the bits past the width in the last word of each row.
+    fn mask_rows(&mut self) {
+        if !self.width.is_multiple_of(BITS) {
+            let mask = (1 << (self.width % BITS)) - 1;
+            for row in self.rows_mut() {
+                row[row.len() - 1] &= mask;
+            }
+        }
+    }
+
+    fn zip_words(&mut self, other: &BitGrid, f: impl Fn(u64, u64) -> u64) {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "bit grids must have the same size"
+        );
+        for (word, &other) in self.words.iter_mut().zip(&other.words) {
+            *word = f(*word, other);
+        }
+    }
+}
+
+fn bit(x: usize) -> u64 {
+    1 << (x % BITS)
+}
+
+macro_rules! impl_bit_ops {
+    ($($op:ident $op_fn:ident $assign:ident $assign_fn:ident $sym:tt),+) => {
+        $(
+            /// # Panics
+            ///
+            /// If the grids differ in size.
+            impl $assign<&BitGrid> for BitGrid {
+                fn $assign_fn(&mut self, rhs: &BitGrid) {
+                    self.zip_words(rhs, |a, b| a $sym b);
+                }
+            }
+
+            impl $op for &BitGrid {
+                type Output = BitGrid;
+
+                fn $op_fn(self, rhs: Self) -> BitGrid {
+                    let mut grid = self.clone();
+                    $assign::$assign_fn(&mut grid, rhs);
+                    grid
+                }
+            }
+        )+
+    };
+}
+
+impl_bit_ops!(
+    BitAnd bitand BitAndAssign bitand_assign &,
+    BitOr bitor BitOrAssign bitor_assign |,
+    BitXor bitxor BitXorAssign bitxor_assign ^
+);
+
+/// Prints set bits as `#` and unset bits as `.`.
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                write!(f, "{}", if self.get((x, y)) { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::BitGrid;
+    use crate::Grid;
+
+    #[test]
+    fn sets_bits() {
+        let mut grid = BitGrid::new(130, 3);
+        assert!(grid.insert((0, 0)));
+        assert!(grid.insert((129, 2)));
+        assert!(!grid.insert((129, 2)));
+        grid.set((64, 1), true);
+        grid.toggle((63, 1));
+
+        assert!(grid.get((64, 1)) && grid.get((63, 1)));
+        assert!(!grid.get((65, 1)) && !grid.get((130, 0)));
+        assert_eq!(grid.count(), 4);
+        assert_eq!(grid.count_row(1), 2);
+        assert_eq!(
+            grid.iter().collect::<Vec<_>>(),
+            vec![(0, 0), (63, 1), (64, 1), (129, 2)]
+        );
+
+        assert!(grid.remove((0, 0)));
+        assert!(!grid.remove((0, 0)));
+        grid.clear();
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_positions_outside() {
+        BitGrid::new(3, 3).insert((3, 0));
+    }
+
+    #[test]
+    fn combines_grids() {
+        let a = BitGrid::from_fn(70, 2, |(x, _)| x % 2 == 0);
+        let b = BitGrid::from_fn(70, 2, |(_, y)| y == 0);
+
+        assert_eq!((&a & &b).count(), 35);
+        assert_eq!((&a | &b).count(), 105);
+        assert_eq!((&a ^ &b).count(), 70);
+
+        let mut c = a.clone();
+        c ^= &a;
+        assert!(c.is_empty());
+    }
+
+    #[test]
+    fn shifts() {
+        let mut grid = BitGrid::from_fn(100, 3, |(x, y)| y == 1 && (x == 0 || x == 60 || x == 99));
+
+        grid.shift_east(5);
+        assert_eq!(grid.iter().collect::<Vec<_>>(), vec![(5, 1), (65, 1)]);
+
+        grid.shift_west(70);
+        assert_eq!(grid.iter().collect::<Vec<_>>(), vec![]);
+
+        let mut grid = BitGrid::from_fn(100, 3, |(x, y)| y == 1 && (x == 3 || x == 70));
+        grid.shift_west(3);
+        assert_eq!(grid.iter().collect::<Vec<_>>(), vec![(0, 1), (67, 1)]);
+
+        grid.shift_south(1);
+        assert_eq!(grid.iter().collect::<Vec<_>>(), vec![(0, 2), (67, 2)]);
+        grid.shift_north(2);
+        assert_eq!(grid.iter().collect::<Vec<_>>(), vec![(0, 0), (67, 0)]);
+        grid.shift_north(5);
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn converts_grids() {
+        let grid = Grid::from_bytes("#.#\n.#.\n");
+        let bits = BitGrid::from_grid(&grid, |&c| c == b'#');
+        assert_eq!(bits.count(), 3);
+        assert_eq!(bits.to_string(), "#.#\n.#.\n");
+    }
+}
@@ -0,0 +1,206 @@
+//! Compass directions on a grid, with `y` growing to the south.
+
+use std::{error::Error, fmt::Display};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// The 4 directions along the axes, clockwise starting in the north.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// All 8 directions including diagonals, clockwise starting in the north.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn from_index(index: usize) -> Direction {
+        Self::ALL[index % Self::ALL.len()]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    pub fn opposite(self) -> Direction {
+        Self::from_index(self.index() + 4)
+    }
+
+    /// Turns right by 90 degrees.
+    pub fn rotate_clockwise(self) -> Direction {
+        Self::from_index(self.index() + 2)
+    }
+
+    /// Turns left by 90 degrees.
+    pub fn rotate_counterclockwise(self) -> Direction {
+        Self::from_index(self.index() + 6)
+    }
+
+    /// Turns right by 45 degrees, e.g. from north to north-east.
+    pub fn rotate_clockwise_45(self) -> Direction {
+        Self::from_index(self.index() + 1)
+    }
+
+    /// Turns left by 45 degrees, e.g. from north to north-west.
+    pub fn rotate_counterclockwise_45(self) -> Direction {
+        Self::from_index(self.index() + 7)
+    }
+
+    /// The offset of a step as `(x, y)`.
+    pub fn delta(self) -> (i32, i32) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::NorthEast => (1, -1),
+            Direction::East => (1, 0),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (0, 1),
+            Direction::SouthWest => (-1, 1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (-1, -1),
+        }
+    }
+
+    /// The offset of a step as `(row, column)`.
+    pub fn row_col(self) -> (i32, i32) {
+        let (x, y) = self.delta();
+        (y, x)
+    }
+
+    /// The offset of a step in a row-major grid with `stride` cells per row,
+    /// e.g. `width + 1` for the bytes of an input that still contain newlines.
+    pub fn offset(self, stride: usize) -> isize {
+        let (x, y) = self.delta();
+        y as isize * stride as isize + x as isize
+    }
+
+    /// The arrow of the direction, as used in puzzle inputs. Diagonals have no arrow and return `None`.
+    pub fn arrow(self) -> Option<u8> {
+        match self {
+            Direction::North => Some(b'^'),
+            Direction::East => Some(b'>'),
+            Direction::South => Some(b'v'),
+            Direction::West => Some(b'<'),
+            _ => None,
+        }
+    }
+}
+
+/// Parses arrows `^>v<`, compass letters `NESW` and keypad letters `URDL`.
+impl TryFrom<u8> for Direction {
+    type Error = DirectionFromByteError;
+
+    fn try_from(b: u8) -> Result<Self, Self::Error> {
+        match b {
+            b'^' | b'N' | b'U' => Ok(Direction::North),
+            b'>' | b'E' | b'R' => Ok(Direction::East),
+            b'v' | b'S' | b'D' => Ok(Direction::South),
+            b'<' | b'W' | b'L' => Ok(Direction::West),
+            _ => Err(DirectionFromByteError(b)),
+        }
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = DirectionFromByteError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        u8::try_from(c)
+            .map_err(|_| DirectionFromByteError(b'?'))
+            .and_then(Direction::try_from)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DirectionFromByteError(u8);
+
+impl Error for DirectionFromByteError {}
+
+impl Display for DirectionFromByteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}` is not a direction, expected one of `^>v<`, `NESW` or `URDL`.",
+            char::from(self.0)
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Direction;
+
+    #[test]
+    fn parses_directions() {
+        assert_eq!(Direction::try_from(b'^'), Ok(Direction::North));
+        assert_eq!(Direction::try_from(b'R'), Ok(Direction::East));
+        assert_eq!(Direction::try_from('v'), Ok(Direction::South));
+        assert_eq!(Direction::try_from(b'W'), Ok(Direction::West));
+        assert!(Direction::try_from(b'\n').is_err());
+        assert!(Direction::try_from('→').is_err());
+
+        for direction in Direction::CARDINAL {
+            assert_eq!(
+                Direction::try_from(direction.arrow().unwrap()),
+                Ok(direction)
+            );
+        }
+    }
+
+    #[test]
+    fn turns() {
+        assert_eq!(Direction::West.rotate_clockwise(), Direction::North);
+        assert_eq!(Direction::North.rotate_counterclockwise(), Direction::West);
+        assert_eq!(Direction::NorthWest.rotate_clockwise_45(), Direction::North);
+        assert_eq!(
+            Direction::North.rotate_counterclockwise_45(),
+            Direction::NorthWest
+        );
+        assert_eq!(Direction::SouthEast.opposite(), Direction::NorthWest);
+
+        for direction in Direction::ALL {
+            assert_eq!(
+                direction.rotate_clockwise().rotate_clockwise(),
+                direction.opposite()
+            );
+            let (x, y) = direction.delta();
+            assert_eq!(direction.opposite().delta(), (-x, -y));
+        }
+    }
+
+    #[test]
+    fn converts_to_offsets() {
+        assert_eq!(Direction::North.delta(), (0, -1));
+        assert_eq!(Direction::North.row_col(), (-1, 0));
+        assert_eq!(Direction::North.offset(11), -11);
+        assert_eq!(Direction::SouthWest.offset(11), 10);
+        assert!(Direction::CARDINAL.iter().all(|d| !d.is_diagonal()));
+        assert_eq!(Direction::ALL.iter().filter(|d| d.is_diagonal()).count(), 4);
+    }
+}
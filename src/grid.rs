@@ -0,0 +1,456 @@
+//! A rectangular grid of cells, indexed by `(x, y)` with the origin in the top left corner.
+
+use std::{
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+};
+
+use crate::{template, Direction, Point, ALL_DIRECTIONS};
+
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Grid<T = bool, C = Vec<T>> {
+    width: usize,
+    height: usize,
+    stride: usize,
+    cells: C,
+    cell: PhantomData<T>,
+}
+
+impl<T, C: AsRef<[T]>> Grid<T, C> {
+    /// Creates a grid from cells stored row by row, each row `stride` cells after the previous one.
+    ///
+    /// # Panics
+    ///
+    /// If the stride is less than the width or there are not enough cells for the last row.
+    pub fn from_cells(width: usize, height: usize, stride: usize, cells: C) -> Self {
+        assert!(stride >= width, "stride is less than the width");
+        assert!(
+            height == 0 || cells.as_ref().len() >= (height - 1) * stride + width,
+            "not enough cells for a {width}x{height} grid"
+        );
+
+        Self {
+            width,
+            height,
+            stride,
+            cells,
+            cell: PhantomData,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: impl Into<Point<usize>>) -> bool {
+        let Point { x, y } = pos.into();
+        x < self.width && y < self.height
+    }
+
+    /// Returns the cell at `(x, y)`, or `None` if it is out of bounds.
+    pub fn get(&self, pos: impl Into<Point<usize>>) -> Option<&T> {
+        let Point { x, y } = pos.into();
+        self.contains((x, y))
+            .then(|| &self.cells.as_ref()[y * self.stride + x])
+    }
+
+    /// Returns the cell at `(x, y)` without bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// `(x, y)` has to be inside of the grid, see [`Grid::contains`].
+    pub unsafe fn get_unchecked(&self, pos: impl Into<Point<usize>>) -> &T {
+        let Point { x, y } = pos.into();
+        self.cells.as_ref().get_unchecked(y * self.stride + x)
+    }
+
+    /// Returns the in-bounds neighbours of `(x, y)` in the order north, east, south, west.
+    pub fn neighbours(
+        &self,
+        pos: impl Into<Point<usize>>,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.steps(pos.into(), &Direction::CARDINAL)
+    }
+
+    /// Returns the in-bounds neighbours of `(x, y)` including diagonals, clockwise starting in the north.
+    pub fn neighbours_diagonal(
+        &self,
+        pos: impl Into<Point<usize>>,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.steps(pos.into(), &Direction::ALL)
+    }
+
+    fn steps<'a>(
+        &'a self,
+        pos: Point<usize>,
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        directions.iter().filter_map(move |&direction| {
+            let next = pos.checked_add_direction(direction)?;
+            self.contains(next).then_some(next.into())
+        })
+    }
+
+    /// Returns row `y`, without the cells between rows like newlines.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells.as_ref()[y * self.stride..y * self.stride + self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// Returns the cells of column `x` from top to bottom.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is out of bounds");
+        self.cells
+            .as_ref()
+            .iter()
+            .skip(x)
+            .step_by(self.stride)
+            .take(self.height)
+    }
+
+    /// Returns all positions and their cells, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.rows()
+            .enumerate()
+            .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, cell)| ((x, y), cell)))
+    }
+
+    /// Returns the position of the first cell equal to `value`, row by row.
+    pub fn find(&self, value: &T) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .find_map(|(pos, cell)| (cell == value).then_some(pos))
+    }
+
+    /// Returns the positions of all cells equal to `value`, row by row.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = (usize, usize)> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter_map(move |(pos, cell)| (cell == value).then_some(pos))
+    }
+
+    /// Creates a grid with the result of `f` for each cell.
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> Grid<U> {
+        let cells = self.rows().flatten().map(&mut f).collect();
+        Grid::from_cells(self.width, self.height, self.width, cells)
+    }
+
+    /// Mirrors the grid at its main diagonal, so that rows become columns.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Grid::from_cells(self.height, self.width, self.height, cells)
+    }
+
+    /// Rotates the grid by 90 degrees clockwise.
+    pub fn rotate_clockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| {
+                (0..self.height)
+                    .rev()
+                    .map(move |y| self.cells.as_ref()[y * self.stride + x].clone())
+            })
+            .collect();
+        Grid::from_cells(self.height, self.width, self.height, cells)
+    }
+
+    /// Rotates the grid by 90 degrees counterclockwise.
+    pub fn rotate_counterclockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Grid::from_cells(self.height, self.width, self.height, cells)
+    }
+}
+
+impl<T, C: AsRef<[T]> + AsMut<[T]>> Grid<T, C> {
+    /// Returns the cell at `(x, y)` mutably, or `None` if it is out of bounds.
+    pub fn get_mut(&mut self, pos: impl Into<Point<usize>>) -> Option<&mut T> {
+        let Point { x, y } = pos.into();
+        if self.contains((x, y)) {
+            Some(&mut self.cells.as_mut()[y * self.stride + x])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the cell at `(x, y)` mutably without bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// `(x, y)` has to be inside of the grid, see [`Grid::contains`].
+    pub unsafe fn get_unchecked_mut(&mut self, pos: impl Into<Point<usize>>) -> &mut T {
+        let Point { x, y } = pos.into();
+        self.cells.as_mut().get_unchecked_mut(y * self.stride + x)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self::from_cells(width, height, width, vec![value; width * height])
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parses the lines of a text grid, converting each byte with `cell`.
+    /// Parsing stops at the first empty line, e.g. before the moves of day 15.
+    ///
+    /// # Panics
+    ///
+    /// If the lines have different lengths.
+    pub fn parse(input: &str, cell: impl FnMut(u8) -> T) -> Self {
+        let lines: Vec<&[u8]> = input
+            .lines()
+            .take_while(|line| !line.is_empty())
+            .map(str::as_bytes)
+            .collect();
+        let width = lines.first().map_or(0, |line| line.len());
+        assert!(
+            lines.iter().all(|line| line.len() == width),
+            "lines of the grid have different lengths"
+        );
+
+        let cells = lines
+            .iter()
+            .flat_map(|line| line.iter().copied())
+            .map(cell)
+            .collect();
+        Self::from_cells(width, lines.len(), width, cells)
+    }
+}
+
+impl<'a> Grid<u8, &'a [u8]> {
+    /// Borrows the bytes of a text grid including their newlines, without copying them.
+    /// The grid ends at the first empty line. Windows line endings are not supported.
+    pub fn from_bytes(input: &'a str) -> Self {
+        let bytes = input.as_bytes();
+        let width = bytes
+            .iter()
+            .position(|&b| b == b'\n')
+            .unwrap_or(bytes.len());
+        let stride = width + 1;
+
+        let end = bytes
+            .windows(2)
+            .position(|w| w == b"\n\n")
+            .map_or(bytes.len(), |i| i + 1);
+        let height = end.div_ceil(stride);
+
+        Self::from_cells(width, height, stride, &bytes[..end])
+    }
+}
+
+impl<T, C: AsRef<[T]>, P: Into<Point<usize>>> Index<P> for Grid<T, C> {
+    type Output = T;
+
+    fn index(&self, pos: P) -> &Self::Output {
+        let pos = pos.into();
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is out of bounds"))
+    }
+}
+
+impl<T, C: AsRef<[T]> + AsMut<[T]>, P: Into<Point<usize>>> IndexMut<P> for Grid<T, C> {
+    fn index_mut(&mut self, pos: P) -> &mut Self::Output {
+        let pos = pos.into();
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is out of bounds"))
+    }
+}
+
+/// An obstacle map, where `true` marks an obstacle.
+impl Grid<bool> {
+    pub fn new(width: u32, height: u32, obstacle_locations: &[(u32, u32)]) -> Self {
+        let mut grid = Self::filled(width as usize, height as usize, false);
+
+        obstacle_locations.iter().for_each(|(x, y)| {
+            grid.insert(*x, *y);
+        });
+
+        grid
+    }
+
+    pub fn next_moves(&self, x: u32, y: u32) -> Vec<(u32, u32)> {
+        self.next_moves_equal_weight(x, y, 0)
+            .into_iter()
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    pub fn next_moves_equal_weight(&self, x: u32, y: u32, weight: u32) -> Vec<((u32, u32), u32)> {
+        ALL_DIRECTIONS
+            .iter()
+            .filter_map(|dir| {
+                let new_pos = (x as i32 + dir.delta().0, y as i32 + dir.delta().1);
+
+                if new_pos.0 < 0
+                    || new_pos.0 >= self.width as i32
+                    || new_pos.1 < 0
+                    || new_pos.1 >= self.height as i32
+                {
+                    return None;
+                }
+
+                let new_offset = self.convert_offset(new_pos.0 as u32, new_pos.1 as u32);
+                if self.cells[new_offset] {
+                    return None;
+                }
+
+                Some(((new_pos.0 as u32, new_pos.1 as u32), weight))
+            })
+            .collect::<Vec<_>>()
+    }
+
+    pub fn convert_offset(&self, x: u32, y: u32) -> usize {
+        y as usize * self.stride + x as usize
+    }
+
+    pub fn insert(&mut self, x: u32, y: u32) {
+        self[(x as usize, y as usize)] = true;
+    }
+
+    pub fn print(&self) {
+        for row in self.rows() {
+            let line: String = row
+                .iter()
+                .map(|&obstacle| if obstacle { '#' } else { '.' })
+                .collect();
+            println!("{line}");
+        }
+    }
+
+    /// The grid as a frame for [`template::visualize::emit`], with obstacles drawn as `#`.
+    pub fn frame(&self) -> template::visualize::Frame {
+        template::visualize::Frame::from_fn(self.width, self.height, |x, y| {
+            if self[(x, y)] {
+                b'#'
+            } else {
+                b'.'
+            }
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Grid;
+    use crate::Point;
+
+    const INPUT: &str = "#.#\n..@\n\nmoves";
+
+    #[test]
+    fn borrows_bytes_with_newlines() {
+        let grid = Grid::from_bytes(INPUT);
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get((2, 1)), Some(&b'@'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.row(1), b"..@");
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), b"#@");
+        assert_eq!(grid.find(&b'@'), Some((2, 1)));
+        assert_eq!(unsafe { *grid.get_unchecked((0, 1)) }, b'.');
+    }
+
+    #[test]
+    fn handles_missing_trailing_newline() {
+        let grid = Grid::from_bytes("ab\ncd");
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid[(1, 1)], b'd');
+    }
+
+    #[test]
+    fn parses_cells() {
+        let mut grid = Grid::parse(INPUT, |b| b == b'#');
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(
+            grid.find_all(&true).collect::<Vec<_>>(),
+            vec![(0, 0), (2, 0)]
+        );
+
+        grid[(1, 1)] = true;
+        assert_eq!(grid.get_mut((1, 1)).copied(), Some(true));
+        assert_eq!(grid.get_mut((1, 2)), None);
+    }
+
+    #[test]
+    fn iterates_neighbours() {
+        let grid = Grid::filled(3, 3, 0);
+        assert_eq!(
+            grid.neighbours((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours((1, 1)).count(), 4);
+        assert_eq!(
+            grid.neighbours_diagonal((2, 0)).collect::<Vec<_>>(),
+            vec![(2, 1), (1, 1), (1, 0)]
+        );
+        assert_eq!(grid.neighbours_diagonal((1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn transposes_and_rotates() {
+        let grid = Grid::from_bytes("abc\ndef\n");
+
+        let transposed = grid.transpose();
+        assert_eq!(
+            transposed.rows().collect::<Vec<_>>(),
+            vec![b"ad", b"be", b"cf"]
+        );
+
+        let clockwise = grid.rotate_clockwise();
+        assert_eq!(
+            clockwise.rows().collect::<Vec<_>>(),
+            vec![b"da", b"eb", b"fc"]
+        );
+
+        let counterclockwise = grid.rotate_counterclockwise();
+        assert_eq!(
+            counterclockwise.rows().collect::<Vec<_>>(),
+            vec![b"cf", b"be", b"ad"]
+        );
+        assert_eq!(counterclockwise.rotate_clockwise(), grid.map(|&b| b));
+    }
+
+    #[test]
+    fn accepts_points() {
+        let mut grid = Grid::parse(INPUT, |b| b);
+        let robot = Point::new(2, 1);
+        assert_eq!(grid[robot], b'@');
+        assert!(grid.contains(robot) && !grid.contains(Point::new(0, 2)));
+
+        grid[robot] = b'.';
+        assert_eq!(grid.get(Point::new(2, 1)), Some(&b'.'));
+        assert_eq!(grid.neighbours(robot).count(), 2);
+    }
+
+    #[test]
+    fn keeps_obstacle_moves() {
+        let grid = Grid::new(3, 2, &[(1, 0)]);
+        assert_eq!(grid.next_moves(0, 0), vec![(0, 1)]);
+        assert_eq!(grid.next_moves_equal_weight(1, 1, 5).len(), 2);
+        assert_eq!(grid.frame().to_string(), ".#.\n...\n");
+    }
+}
@@ -1,79 +1,168 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, crosscheck, download, leaderboard, read, scaffold, solve, time,
+};
+use advent_of_code::template::InputSource;
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::today;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{
+        config::{self, Overrides},
+        visualize::VisualizeTarget,
+        Day, InputSource, Profile, Year,
+    };
     use std::process;
 
     pub enum AppArguments {
         Download {
+            year: Option<Year>,
             day: Day,
         },
         Read {
+            year: Option<Year>,
             day: Day,
         },
+        Leaderboard {
+            year: Option<Year>,
+            id: u32,
+        },
         Scaffold {
+            year: Option<Year>,
             day: Day,
             download: bool,
             overwrite: bool,
+            from: Option<Day>,
         },
         Solve {
+            year: Option<Year>,
             day: Day,
+            release: bool,
             dhat: bool,
+            part: Option<u8>,
             submit: Option<u8>,
+            profile: Option<Profile>,
+            source: InputSource,
+            visualize: Option<VisualizeTarget>,
         },
         All {
+            year: Option<Year>,
             release: bool,
+            profile: Option<Profile>,
         },
         Time {
+            year: Option<Year>,
             all: bool,
             day: Option<Day>,
             store: bool,
+            part: Option<u8>,
+            profile: Option<Profile>,
+        },
+        Crosscheck {
+            year: Option<Year>,
+            day: Option<Day>,
+            release: bool,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
+    }
+
+    /// Whether to build in release mode. `--release` and `--debug` win over `runner.release` in `aoc.toml`,
+    /// which wins over `default`.
+    fn release(args: &mut pico_args::Arguments, default: bool) -> bool {
+        if args.contains("--debug") {
+            false
+        } else if args.contains("--release") {
+            true
+        } else {
+            config::get().release.unwrap_or(default)
+        }
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
+        // read `aoc.toml` before anything depends on it.
+        config::init(Overrides {
+            data_dir: args.opt_value_from_str("--data-dir")?,
+            template: args.opt_value_from_str("--template")?,
+            bench_budget_ms: args.opt_value_from_str("--budget")?,
+            timeout_secs: args.opt_value_from_str("--timeout")?,
+        });
+        // the default year is stored in the single-year layout, see `Year::qualified`.
+        let year = Year::qualified(args.opt_value_from_str("--year")?);
+
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
-                release: args.contains("--release"),
+                year,
+                release: release(&mut args, false),
+                profile: args.opt_value_from_str("--profile")?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let part = args.opt_value_from_str("--part")?;
+                let profile = args.opt_value_from_str("--profile")?;
 
                 AppArguments::Time {
+                    year,
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    part,
+                    profile,
                 }
             }
+            Some("crosscheck") => AppArguments::Crosscheck {
+                year,
+                release: release(&mut args, true),
+                day: args.opt_free_from_str()?,
+            },
             Some("download") => AppArguments::Download {
+                year,
                 day: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
+                year,
                 day: args.free_from_str()?,
             },
+            Some("leaderboard") => AppArguments::Leaderboard {
+                year,
+                id: args.free_from_str()?,
+            },
             Some("scaffold") => AppArguments::Scaffold {
+                year,
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                from: args.opt_value_from_str("--from")?,
             },
             Some("solve") => AppArguments::Solve {
+                year,
                 day: args.free_from_str()?,
+                release: release(&mut args, true),
+                part: args.opt_value_from_str("--part")?,
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                profile: args.opt_value_from_str("--profile")?,
+                source: {
+                    let example = args.contains("--example");
+                    let example_part = args.opt_value_from_str("--example-part")?;
+                    let input = args.opt_value_from_str("--input")?;
+                    InputSource::from_flags(example, example_part, input).unwrap_or_else(|e| {
+                        eprintln!("{e}");
+                        process::exit(1);
+                    })
+                },
+                visualize: args.opt_value_from_str("--visualize")?,
             },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today {
+                wait: args.contains("--wait"),
+            },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -100,38 +189,67 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::All {
+                year,
+                release,
+                profile,
+            } => all::handle(year, release, profile.as_ref()),
+            AppArguments::Time {
+                year,
+                day,
+                all,
+                store,
+                part,
+                profile,
+            } => time::handle(year, day, all, store, part, profile.as_ref()),
+            AppArguments::Crosscheck { year, day, release } => {
+                crosscheck::handle(year, day, release);
+            }
+            AppArguments::Download { year, day } => download::handle(year, day),
+            AppArguments::Read { year, day } => read::handle(year, day),
+            AppArguments::Leaderboard { year, id } => leaderboard::handle(year, id),
             AppArguments::Scaffold {
+                year,
                 day,
                 download,
                 overwrite,
+                from,
             } => {
-                scaffold::handle(day, overwrite);
+                // download first so the template can use the puzzle title.
                 if download {
-                    download::handle(day);
+                    download::handle(year, day);
                 }
+                scaffold::handle(year, day, overwrite, from);
             }
-            AppArguments::Solve { day, dhat, submit } => solve::handle(day, true, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+            AppArguments::Solve {
+                year,
+                day,
+                release,
+                dhat,
+                part,
+                submit,
+                profile,
+                source,
+                visualize,
+            } => {
+                if submit.is_some() && source != InputSource::Puzzle {
+                    eprintln!("Only answers for the puzzle input can be submitted.");
+                    std::process::exit(1);
+                }
+                solve::handle(
+                    year,
+                    day,
+                    release,
+                    dhat,
+                    part,
+                    submit,
+                    profile.as_ref(),
+                    &source,
+                    visualize.as_ref(),
+                );
             }
+            #[cfg(feature = "today")]
+            AppArguments::Today { wait } => today::handle(wait),
         },
     };
 }
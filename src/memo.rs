@@ -0,0 +1,239 @@
+//! Memoisation for recursive solutions, scoped to a single solve call.
+
+use std::{
+    fmt::Display,
+    hash::Hash,
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+use dashmap::DashMap;
+use rustc_hash::{FxBuildHasher, FxHashMap};
+
+/// Where a [`Memo`] keeps its results. Stores are shared between threads, so they use interior mutability.
+pub trait Store<K, V>: Default {
+    fn get(&self, key: &K) -> Option<V>;
+    fn insert(&self, key: K, value: V);
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// One map shared by all threads, split into shards that are locked separately.
+/// Results computed on one thread are reused by all others.
+pub struct Sharded<K, V>(DashMap<K, V, FxBuildHasher>);
+
+impl<K: Eq + Hash, V> Default for Sharded<K, V> {
+    fn default() -> Self {
+        Self(DashMap::with_hasher(FxBuildHasher))
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Store<K, V> for Sharded<K, V> {
+    fn get(&self, key: &K) -> Option<V> {
+        self.0.get(key).map(|value| value.clone())
+    }
+
+    fn insert(&self, key: K, value: V) {
+        self.0.insert(key, value);
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// A separate map for each rayon worker thread, plus one for all other threads.
+/// Avoids contention when results are cheap to recompute, at the cost of computing them once per thread.
+pub struct PerThread<K, V>(Vec<Mutex<FxHashMap<K, V>>>);
+
+impl<K, V> PerThread<K, V> {
+    fn local(&self) -> &Mutex<FxHashMap<K, V>> {
+        let index = rayon::current_thread_index().unwrap_or(self.0.len() - 1);
+        &self.0[index.min(self.0.len() - 1)]
+    }
+}
+
+impl<K, V> Default for PerThread<K, V> {
+    fn default() -> Self {
+        Self(
+            (0..=rayon::current_num_threads())
+                .map(|_| Mutex::default())
+                .collect(),
+        )
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Store<K, V> for PerThread<K, V> {
+    fn get(&self, key: &K) -> Option<V> {
+        self.local().lock().unwrap().get(key).cloned()
+    }
+
+    fn insert(&self, key: K, value: V) {
+        self.local().lock().unwrap().insert(key, value);
+    }
+
+    fn len(&self) -> usize {
+        self.0.iter().map(|map| map.lock().unwrap().len()).sum()
+    }
+}
+
+/// Cached results of a function, see the module documentation.
+pub struct Memo<K, V, S = Sharded<K, V>> {
+    store: S,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+    entry: PhantomData<fn(K) -> V>,
+}
+
+impl<K, V, S: Store<K, V>> Default for Memo<K, V, S> {
+    fn default() -> Self {
+        Self {
+            store: S::default(),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+            entry: PhantomData,
+        }
+    }
+}
+
+impl<K, V, S: Store<K, V>> Memo<K, V, S> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached result for `key`, or computes and caches it.
+    /// No lock is held while `compute` runs, so it may recurse into the same memo.
+    pub fn get_or_insert_with(&self, key: K, compute: impl FnOnce() -> V) -> V
+    where
+        V: Clone,
+    {
+        if let Some(value) = self.store.get(&key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return value;
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let value = compute();
+        self.store.insert(key, value.clone());
+        value
+    }
+
+    /// The number of cached results.
+    pub fn len(&self) -> usize {
+        self.store.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.store.is_empty()
+    }
+
+    pub fn stats(&self) -> Stats {
+        Stats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.len(),
+        }
+    }
+}
+
+/// Runs `f` with an empty [`Sharded`] memo that is dropped afterwards.
+pub fn sharded<K: Eq + Hash, V: Clone, R>(f: impl FnOnce(&Memo<K, V, Sharded<K, V>>) -> R) -> R {
+    f(&Memo::new())
+}
+
+/// Runs `f` with an empty [`PerThread`] memo that is dropped afterwards.
+pub fn per_thread<K: Eq + Hash, V: Clone, R>(
+    f: impl FnOnce(&Memo<K, V, PerThread<K, V>>) -> R,
+) -> R {
+    f(&Memo::new())
+}
+
+/// How often a [`Memo`] found a cached result.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: usize,
+    pub misses: usize,
+    pub entries: usize,
+}
+
+impl Stats {
+    /// The share of lookups that found a cached result, between 0 and 1.
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate), {} entries",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0,
+            self.entries
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use rayon::prelude::*;
+
+    use super::{per_thread, sharded, Memo, Store};
+
+    fn fibonacci<S: Store<u64, u64>>(n: u64, memo: &Memo<u64, u64, S>) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get_or_insert_with(n, || fibonacci(n - 1, memo) + fibonacci(n - 2, memo))
+    }
+
+    #[test]
+    fn memoises_recursion() {
+        let stats = sharded(|memo| {
+            assert_eq!(fibonacci(90, memo), 2880067194370816120);
+            memo.stats()
+        });
+        assert_eq!((stats.hits, stats.misses, stats.entries), (87, 89, 89));
+        assert_eq!(
+            stats.to_string(),
+            "87 hits, 89 misses (49.4% hit rate), 89 entries"
+        );
+
+        // every scope starts empty.
+        sharded(|memo: &Memo<u64, u64>| assert!(memo.is_empty()));
+    }
+
+    #[test]
+    fn shares_between_threads() {
+        let sums = sharded(|memo| {
+            let sums: Vec<u64> = (0..64_u64)
+                .into_par_iter()
+                .map(|n| fibonacci(n % 40, memo))
+                .collect();
+            assert!(memo.len() <= 39);
+            sums
+        });
+
+        let per_thread_sums = per_thread(|memo| {
+            (0..64_u64)
+                .into_par_iter()
+                .map(|n| fibonacci(n % 40, memo))
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(sums, per_thread_sums);
+        assert_eq!(sums[39], 63245986);
+    }
+}
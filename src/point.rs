@@ -0,0 +1,216 @@
+//! A 2D point or vector with `x` growing to the east and `y` growing to the south, like the rows of a puzzle input.
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::Direction;
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point<T = i32> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// Converts both coordinates, or returns `None` if one of them does not fit into `U`.
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Point<U>> {
+        Some(Point::new(
+            U::try_from(self.x).ok()?,
+            U::try_from(self.y).ok()?,
+        ))
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Point<T> {
+    /// The distance when moving along the axes.
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// The distance when also moving diagonally.
+    pub fn chebyshev(self, other: Self) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+
+    /// The squared straight-line distance, which avoids floats and keeps the order of distances.
+    pub fn euclidean_squared(self, other: Self) -> T {
+        let (dx, dy) = (abs_diff(self.x, other.x), abs_diff(self.y, other.y));
+        dx * dx + dy * dy
+    }
+}
+
+/// Works for unsigned types, where `a - b` would underflow.
+fn abs_diff<T: Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T: Copy> From<&(T, T)> for Point<T> {
+    fn from(&(x, y): &(T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+/// The step into a direction, see [`Direction::delta`].
+impl From<Direction> for Point<i32> {
+    fn from(direction: Direction) -> Self {
+        direction.delta().into()
+    }
+}
+
+macro_rules! impl_direction_ops {
+    ($($t:ty),+) => {
+        $(
+            impl Add<Direction> for Point<$t> {
+                type Output = Self;
+
+                fn add(self, direction: Direction) -> Self::Output {
+                    let delta = Point::from(direction);
+                    Self::new(self.x + delta.x as $t, self.y + delta.y as $t)
+                }
+            }
+
+            impl AddAssign<Direction> for Point<$t> {
+                fn add_assign(&mut self, direction: Direction) {
+                    *self = *self + direction;
+                }
+            }
+        )+
+    };
+}
+
+impl_direction_ops!(i32, i64, isize);
+
+impl Point<usize> {
+    /// Moves one step into `direction`, or returns `None` when leaving the first quadrant, e.g. north of a grid.
+    pub fn checked_add_direction(self, direction: Direction) -> Option<Self> {
+        let delta = Point::from(direction);
+        Some(Self::new(
+            self.x.checked_add_signed(delta.x as isize)?,
+            self.y.checked_add_signed(delta.y as isize)?,
+        ))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Point;
+    use crate::Direction;
+
+    #[test]
+    fn does_arithmetic() {
+        let mut p = Point::new(1, 2) + Point::new(3, -4);
+        assert_eq!(p, Point::new(4, -2));
+        assert_eq!(p - Point::new(1, 1), Point::new(3, -3));
+        assert_eq!(p * 2, Point::new(8, -4));
+        assert_eq!(-p, Point::new(-4, 2));
+
+        p -= Point::new(4, 0);
+        p += Point::new(0, 2);
+        assert_eq!(p, Point::default());
+    }
+
+    #[test]
+    fn moves_into_directions() {
+        let p = Point::new(2_i64, 2);
+        assert_eq!(p + Direction::North, Point::new(2, 1));
+        assert_eq!(p + Direction::East, Point::new(3, 2));
+        assert_eq!(p + Direction::South, Point::new(2, 3));
+        assert_eq!(p + Direction::West, Point::new(1, 2));
+        assert_eq!(p + Direction::NorthWest, Point::new(1, 1));
+
+        let origin = Point::new(0_usize, 0);
+        assert_eq!(origin.checked_add_direction(Direction::North), None);
+        assert_eq!(
+            origin.checked_add_direction(Direction::South),
+            Some(Point::new(0, 1))
+        );
+    }
+
+    #[test]
+    fn measures_distances() {
+        let (a, b) = (Point::new(1_u32, 5), Point::new(4, 1));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.euclidean_squared(b), 25);
+        assert_eq!(b.manhattan(a), 7);
+    }
+
+    #[test]
+    fn converts() {
+        let p: Point<i64> = (3, -1).into();
+        assert_eq!(<(i64, i64)>::from(p), (3, -1));
+        assert_eq!(p.try_cast::<u8>(), None);
+        assert_eq!(
+            Point::new(3_i64, 1).try_cast::<u8>(),
+            Some(Point::new(3, 1))
+        );
+        assert_eq!(Point::from(&(1_u32, 2)), Point::new(1, 2));
+    }
+}
@@ -0,0 +1,314 @@
+//! Sets of integers stored as sorted, disjoint half-open intervals.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Debug,
+    ops::{Add, Range, Sub},
+};
+
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    /// Maps the start of each interval to its end.
+    ranges: BTreeMap<T, T>,
+}
+
+impl<T> RangeSet<T>
+where
+    T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new() -> Self {
+        Self {
+            ranges: BTreeMap::new(),
+        }
+    }
+
+    /// Adds all values of `range`, merging it with overlapping and touching intervals.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let (mut start, mut end) = (range.start, range.end);
+        while let Some((&s, &e)) = self
+            .ranges
+            .range(..=end)
+            .next_back()
+            .filter(|&(_, &e)| e >= start)
+        {
+            self.ranges.remove(&s);
+            start = start.min(s);
+            end = end.max(e);
+        }
+        self.ranges.insert(start, end);
+    }
+
+    /// Removes all values of `range`, splitting intervals that only partly overlap it.
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        loop {
+            let Some((s, e)) = self.overlapping_entries(&range).next() else {
+                break;
+            };
+            self.ranges.remove(&s);
+            if s < range.start {
+                self.ranges.insert(s, range.start);
+            }
+            if e > range.end {
+                self.ranges.insert(range.end, e);
+            }
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.ranges
+            .range(..=value)
+            .next_back()
+            .is_some_and(|(_, &end)| value < end)
+    }
+
+    /// Returns whether every value of `range` is in the set.
+    pub fn contains_range(&self, range: &Range<T>) -> bool {
+        range.is_empty()
+            || self
+                .ranges
+                .range(..=range.start)
+                .next_back()
+                .is_some_and(|(_, &end)| range.end <= end)
+    }
+
+    /// Returns whether any value of `range` is in the set.
+    pub fn overlaps(&self, range: &Range<T>) -> bool {
+        self.overlapping_entries(range).next().is_some()
+    }
+
+    /// The parts of the set that lie within `range`, in ascending order.
+    pub fn overlapping(&self, range: &Range<T>) -> impl Iterator<Item = Range<T>> + '_ {
+        let (start, end) = (range.start, range.end);
+        self.overlapping_entries(range)
+            .map(move |(s, e)| s.max(start)..e.min(end))
+    }
+
+    /// The intervals of the set, in ascending order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = Range<T>> + '_ {
+        self.ranges.iter().map(|(&start, &end)| start..end)
+    }
+
+    /// The number of disjoint intervals.
+    pub fn interval_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// The number of values in the set, i.e. the summed length of all intervals.
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::default(), |len, (&start, &end)| len + (end - start))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.extend(other.iter());
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.iter()
+            .flat_map(|range| other.overlapping(&range).collect::<Vec<_>>())
+            .collect()
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for range in other.iter() {
+            difference.remove(range);
+        }
+        difference
+    }
+
+    /// The gaps between the intervals within `bounds`, i.e. the complement of the set.
+    pub fn gaps(&self, bounds: Range<T>) -> Self {
+        let mut gaps = Self::from_iter([bounds]);
+        for range in self.iter() {
+            gaps.remove(range);
+        }
+        gaps
+    }
+
+    /// The starts and ends of all intervals, e.g. to give each interval and gap its own grid cell.
+    pub fn boundaries(&self) -> Compression<T> {
+        self.ranges
+            .iter()
+            .flat_map(|(&start, &end)| [start, end])
+            .collect()
+    }
+
+    fn overlapping_entries(&self, range: &Range<T>) -> impl Iterator<Item = (T, T)> + '_ {
+        let (start, end) = (range.start, range.end);
+        // the interval before `start` may still reach into the range.
+        let first = self
+            .ranges
+            .range(..start)
+            .next_back()
+            .filter(|&(_, &e)| e > start)
+            .map_or(start, |(&s, _)| s);
+
+        self.ranges
+            .range(first..)
+            .take_while(move |&(&s, _)| s < end && start < end)
+            .map(|(&s, &e)| (s, e))
+    }
+}
+
+impl<T> FromIterator<Range<T>> for RangeSet<T>
+where
+    T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T> Extend<Range<T>> for RangeSet<T>
+where
+    T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn extend<I: IntoIterator<Item = Range<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: Debug> Debug for RangeSet<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set()
+            .entries(self.ranges.iter().map(|(start, end)| start..end))
+            .finish()
+    }
+}
+
+/// Maps sparse coordinates to dense indices, so that e.g. a grid only needs a cell per distinct coordinate.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Compression<T> {
+    coords: Vec<T>,
+}
+
+impl<T: Copy + Ord> Compression<T> {
+    /// The index of `coord`, or `None` if it was not part of the compression.
+    pub fn index(&self, coord: T) -> Option<usize> {
+        self.coords.binary_search(&coord).ok()
+    }
+
+    /// The coordinate at `index`.
+    pub fn get(&self, index: usize) -> Option<T> {
+        self.coords.get(index).copied()
+    }
+
+    /// The distinct coordinates in ascending order.
+    pub fn coords(&self) -> &[T] {
+        &self.coords
+    }
+
+    pub fn len(&self) -> usize {
+        self.coords.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.coords.is_empty()
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<T> for Compression<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut coords: Vec<T> = iter.into_iter().collect();
+        coords.sort_unstable();
+        coords.dedup();
+        Self { coords }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Compression, RangeSet};
+
+    #[test]
+    fn merges_ranges() {
+        let mut set = RangeSet::from_iter([5..8, 0..3, 3..4, 10..12]);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..4, 5..8, 10..12]);
+
+        set.insert(2..11);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..12]);
+        assert_eq!(set.len(), 12);
+
+        set.insert(20..20);
+        assert_eq!(set.interval_count(), 1);
+    }
+
+    #[test]
+    fn removes_ranges() {
+        let mut set = RangeSet::from_iter([0..10, 20..30]);
+        set.remove(5..25);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..5, 25..30]);
+
+        set.remove(2..3);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..2, 3..5, 25..30]);
+        assert_eq!(set.len(), 9);
+
+        set.remove(-10..100);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn queries() {
+        let set = RangeSet::from_iter([0..5, 10..15]);
+        assert!(set.contains(0) && set.contains(14));
+        assert!(!set.contains(5) && !set.contains(-1));
+
+        assert!(set.contains_range(&(10..15)));
+        assert!(!set.contains_range(&(4..11)));
+        assert!(set.overlaps(&(4..11)));
+        assert!(!set.overlaps(&(5..10)));
+        assert_eq!(
+            set.overlapping(&(3..12)).collect::<Vec<_>>(),
+            vec![3..5, 10..12]
+        );
+        assert_eq!(
+            set.gaps(-5..20).iter().collect::<Vec<_>>(),
+            vec![-5..0, 5..10, 15..20]
+        );
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a = RangeSet::from_iter([0_u64..10, 20..30]);
+        let b = RangeSet::from_iter([5_u64..25]);
+
+        assert_eq!(a.union(&b), RangeSet::from_iter([0..30]));
+        assert_eq!(a.intersection(&b), RangeSet::from_iter([5..10, 20..25]));
+        assert_eq!(a.difference(&b), RangeSet::from_iter([0..5, 25..30]));
+        assert_eq!(format!("{:?}", b.difference(&a)), "{10..20}");
+    }
+
+    #[test]
+    fn compresses_coordinates() {
+        let compression = Compression::from_iter([100, -3, 7, 100, 7]);
+        assert_eq!(compression.coords(), &[-3, 7, 100]);
+        assert_eq!(compression.index(100), Some(2));
+        assert_eq!(compression.index(8), None);
+        assert_eq!(compression.get(0), Some(-3));
+
+        let set = RangeSet::from_iter([0..5, 10..15]);
+        assert_eq!(set.boundaries().coords(), &[0, 5, 10, 15]);
+    }
+}
@@ -0,0 +1,304 @@
+//! A cursor for parsing numbers and literals out of puzzle inputs, e.g. `p=0,4 v=3,-3`.
+
+use std::{error::Error, fmt::Display};
+
+#[derive(Clone, Debug)]
+pub struct Scanner<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    /// The byte offset of the cursor.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns whether all bytes have been consumed.
+    pub fn is_empty(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    /// The bytes after the cursor.
+    pub fn rest(&self) -> &'a [u8] {
+        &self.bytes[self.pos.min(self.bytes.len())..]
+    }
+
+    pub fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    /// Moves the cursor to the next occurrence of `needle`, without consuming it.
+    pub fn skip_until(&mut self, needle: &[u8]) -> Result<(), ScanError> {
+        match self
+            .rest()
+            .windows(needle.len().max(1))
+            .position(|w| w == needle)
+        {
+            Some(i) => {
+                self.pos += i;
+                Ok(())
+            }
+            None => Err(self.error(self.bytes.len(), &literal(needle))),
+        }
+    }
+
+    /// Consumes `literal`, or returns an error if the bytes at the cursor differ.
+    pub fn expect(&mut self, literal_bytes: &[u8]) -> Result<(), ScanError> {
+        if self.rest().starts_with(literal_bytes) {
+            self.pos += literal_bytes.len();
+            Ok(())
+        } else {
+            Err(self.error(self.pos, &literal(literal_bytes)))
+        }
+    }
+
+    /// Parses the next unsigned number, skipping all bytes before its first digit.
+    pub fn next_uint<T: Number>(&mut self) -> Result<T, ScanError> {
+        if !self.seek_number(false) {
+            return Err(self.error(self.bytes.len(), "a number"));
+        }
+        self.parse_digits(false)
+    }
+
+    /// Parses the next number, skipping all bytes before it. A `-` directly before the digits makes it negative.
+    pub fn next_int<T: Number>(&mut self) -> Result<T, ScanError> {
+        if !self.seek_number(true) {
+            return Err(self.error(self.bytes.len(), "a number"));
+        }
+
+        let negative = self.peek() == Some(b'-');
+        if negative {
+            self.pos += 1;
+        }
+        self.parse_digits(negative)
+    }
+
+    /// Returns all remaining unsigned numbers.
+    ///
+    /// # Panics
+    ///
+    /// If a number overflows `T` in a debug build.
+    pub fn uints<T: Number>(&mut self) -> impl Iterator<Item = T> + use<'_, 'a, T> {
+        std::iter::from_fn(move || {
+            self.seek_number(false)
+                .then(|| self.parse_digits(false).unwrap_or_else(|e| panic!("{e}")))
+        })
+    }
+
+    /// Returns all remaining numbers, see [`Scanner::next_int`].
+    ///
+    /// # Panics
+    ///
+    /// If a number overflows `T` in a debug build.
+    pub fn ints<T: Number>(&mut self) -> impl Iterator<Item = T> + use<'_, 'a, T> {
+        std::iter::from_fn(move || {
+            self.seek_number(true)
+                .then(|| self.next_int().unwrap_or_else(|e| panic!("{e}")))
+        })
+    }
+
+    /// Moves the cursor to the start of the next number and returns whether there is one.
+    fn seek_number(&mut self, signed: bool) -> bool {
+        let rest = self.rest();
+        let start = rest.iter().enumerate().position(|(i, &b)| {
+            b.is_ascii_digit()
+                || (signed && b == b'-' && rest.get(i + 1).is_some_and(u8::is_ascii_digit))
+        });
+
+        match start {
+            Some(i) => {
+                self.pos += i;
+                true
+            }
+            None => {
+                self.pos = self.bytes.len();
+                false
+            }
+        }
+    }
+
+    fn parse_digits<T: Number>(&mut self, negative: bool) -> Result<T, ScanError> {
+        let start = self.pos;
+        let mut n = T::ZERO;
+
+        for &b in self.rest().iter().take_while(|b| b.is_ascii_digit()) {
+            n = n
+                .push_digit(b - b'0', negative)
+                .ok_or_else(|| self.error(start, T::NAME))?;
+            self.pos += 1;
+        }
+
+        if self.pos == start {
+            return Err(self.error(start, "a digit"));
+        }
+        Ok(n)
+    }
+
+    fn error(&self, offset: usize, expected: &str) -> ScanError {
+        let before = &self.bytes[..offset];
+        let line_start = before
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+
+        ScanError {
+            offset,
+            line: before.iter().filter(|&&b| b == b'\n').count() + 1,
+            column: offset - line_start + 1,
+            expected: expected.into(),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for Scanner<'a> {
+    fn from(input: &'a str) -> Self {
+        Self::new(input.as_bytes())
+    }
+}
+
+fn literal(bytes: &[u8]) -> String {
+    format!("`{}`", String::from_utf8_lossy(bytes))
+}
+
+/// A parse error at a position of the input. Lines and columns start at 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScanError {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+impl Error for ScanError {}
+
+impl Display for ScanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "expected {} at line {}, column {} (byte {})",
+            self.expected, self.line, self.column, self.offset
+        )
+    }
+}
+
+/// An integer that can be parsed by a [`Scanner`].
+pub trait Number: Copy {
+    const ZERO: Self;
+    /// Describes the type in errors, e.g. `a number that fits into u8`.
+    const NAME: &'static str;
+
+    /// Appends a decimal digit, subtracting it for negative numbers. Returns `None` on overflow.
+    fn push_digit(self, digit: u8, negative: bool) -> Option<Self>;
+}
+
+macro_rules! impl_number {
+    ($($t:ty),+) => {
+        $(
+            impl Number for $t {
+                const ZERO: Self = 0;
+                const NAME: &'static str = concat!("a number that fits into ", stringify!($t));
+
+                #[inline(always)]
+                fn push_digit(self, digit: u8, negative: bool) -> Option<Self> {
+                    let digit = digit as Self;
+
+                    if cfg!(debug_assertions) {
+                        let shifted = self.checked_mul(10)?;
+                        if negative {
+                            shifted.checked_sub(digit)
+                        } else {
+                            shifted.checked_add(digit)
+                        }
+                    } else {
+                        let shifted = self.wrapping_mul(10);
+                        Some(if negative {
+                            shifted.wrapping_sub(digit)
+                        } else {
+                            shifted.wrapping_add(digit)
+                        })
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_number!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Scanner;
+
+    #[test]
+    fn parses_numbers() {
+        let mut scanner = Scanner::from("p=0,4 v=3,-3\np=6,3 v=-1,-3");
+        assert_eq!(scanner.next_int::<i32>(), Ok(0));
+        assert_eq!(scanner.next_int::<i32>(), Ok(4));
+        assert_eq!(scanner.next_uint::<u8>(), Ok(3));
+        assert_eq!(scanner.next_int::<i64>(), Ok(-3));
+        assert_eq!(
+            scanner.ints::<i32>().collect::<Vec<_>>(),
+            vec![6, 3, -1, -3]
+        );
+        assert!(scanner.is_empty());
+        assert!(scanner.next_int::<i32>().is_err());
+    }
+
+    #[test]
+    fn only_accepts_leading_minus() {
+        let mut scanner = Scanner::from("1-2 - 3 --4");
+        assert_eq!(
+            scanner.ints::<i32>().collect::<Vec<_>>(),
+            vec![1, -2, 3, -4]
+        );
+
+        let mut scanner = Scanner::from("1-2");
+        assert_eq!(scanner.uints::<u32>().collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn parses_literals() {
+        let mut scanner = Scanner::from("Register A: 729\n\nProgram: 0,1");
+        scanner.expect(b"Register A: ").unwrap();
+        assert_eq!(scanner.next_uint::<u64>(), Ok(729));
+
+        scanner.skip_until(b"Program").unwrap();
+        assert_eq!(scanner.rest(), b"Program: 0,1");
+        assert!(scanner.skip_until(b"Register").is_err());
+        assert_eq!(scanner.position(), 17);
+    }
+
+    #[test]
+    fn reports_positions() {
+        let mut scanner = Scanner::from("a: 1\nb: 2");
+        scanner.next_uint::<u8>().unwrap();
+
+        let error = scanner.expect(b"\nc").unwrap_err();
+        assert_eq!((error.offset, error.line, error.column), (4, 1, 5));
+
+        scanner.skip_until(b"2").unwrap();
+        let error = scanner.expect(b"3").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected `3` at line 2, column 4 (byte 8)"
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn reports_overflows() {
+        let error = Scanner::from("x\n 300").next_uint::<u8>().unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.expected, "a number that fits into u8");
+
+        assert_eq!(Scanner::from("-128").next_int::<i8>(), Ok(-128));
+        assert!(Scanner::from("-1").next_int::<u8>().is_err());
+    }
+}
@@ -0,0 +1,292 @@
+//! Graph searches over any state type, keeping all optimal predecessors of each state.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// The result of a search: the cost of each reached state and its optimal predecessors.
+#[derive(Clone, Debug)]
+pub struct Search<N, C> {
+    costs: FxHashMap<N, C>,
+    predecessors: FxHashMap<N, Vec<N>>,
+    goals: Vec<N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Search<N, C> {
+    fn new(start: N, zero: C) -> Self {
+        Self {
+            costs: FxHashMap::from_iter([(start, zero)]),
+            predecessors: FxHashMap::default(),
+            goals: vec![],
+        }
+    }
+
+    /// The cost of the cheapest path to `node`, or `None` if it was not reached.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    /// The cost of every reached state.
+    pub fn costs(&self) -> &FxHashMap<N, C> {
+        &self.costs
+    }
+
+    /// Consumes the search, returning the cost of every reached state.
+    pub fn into_costs(self) -> FxHashMap<N, C> {
+        self.costs
+    }
+
+    /// The goals reached at the lowest cost, see [`astar`].
+    pub fn goals(&self) -> &[N] {
+        &self.goals
+    }
+
+    /// The cost of the cheapest goal, see [`astar`].
+    pub fn goal_cost(&self) -> Option<C> {
+        self.cost(self.goals.first()?)
+    }
+
+    /// The states that precede `node` on a cheapest path to it.
+    pub fn predecessors(&self, node: &N) -> &[N] {
+        self.predecessors.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    /// One cheapest path from the start to `node`, including both.
+    pub fn path(&self, node: &N) -> Option<Vec<N>> {
+        self.costs.get(node)?;
+
+        let mut path = vec![node.clone()];
+        while let Some(previous) = self.predecessors(path.last().unwrap()).first() {
+            path.push(previous.clone());
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// All states on any cheapest path to one of `targets`, including the start and the targets.
+    pub fn optimal_states(&self, targets: impl IntoIterator<Item = N>) -> FxHashSet<N> {
+        let mut visited = FxHashSet::default();
+        let mut stack: Vec<N> = targets
+            .into_iter()
+            .filter(|target| self.costs.contains_key(target))
+            .collect();
+
+        while let Some(node) = stack.pop() {
+            if visited.insert(node.clone()) {
+                stack.extend(self.predecessors(&node).iter().cloned());
+            }
+        }
+
+        visited
+    }
+
+    /// The tiles on any cheapest path to one of `targets`, where `tile` maps a state to its tile,
+    /// e.g. `|&(pos, _direction)| pos`.
+    pub fn tiles<T: Eq + Hash>(
+        &self,
+        targets: impl IntoIterator<Item = N>,
+        tile: impl Fn(&N) -> T,
+    ) -> FxHashSet<T> {
+        self.optimal_states(targets).iter().map(tile).collect()
+    }
+}
+
+/// Explores all states reachable from `start` where each step costs 1.
+pub fn bfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search::new(start.clone(), 0);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        let cost = search.costs[&node] + 1;
+
+        for next in successors(&node) {
+            match search.costs.get(&next) {
+                None => {
+                    search.costs.insert(next.clone(), cost);
+                    search.predecessors.insert(next.clone(), vec![node.clone()]);
+                    queue.push_back(next);
+                }
+                Some(&known) if known == cost => {
+                    search
+                        .predecessors
+                        .entry(next)
+                        .or_default()
+                        .push(node.clone());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    search
+}
+
+/// Explores all states reachable from `start`, where `successors` returns each next state with the cost of the step.
+pub fn dijkstra<N, C, I>(start: N, successors: impl FnMut(&N) -> I) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Default + Ord + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::default(), |_| false)
+}
+
+/// Searches the cheapest paths from `start` to the states for which `is_goal` returns true, see [`Search::goals`].
+/// The search stops after all goals at the lowest cost were found. `heuristic` estimates the remaining cost of
+/// a state and must never overestimate it, e.g. the Manhattan distance to the goal.
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Default + Ord + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search::new(start.clone(), C::default());
+    // the heap holds indices into `nodes`, so that states don't need to be ordered.
+    let mut nodes = vec![start.clone()];
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+    let mut goal_cost = None;
+
+    while let Some(Reverse((estimate, cost, index))) = heap.pop() {
+        if goal_cost.is_some_and(|goal_cost| estimate > goal_cost) {
+            break;
+        }
+
+        let node = nodes[index].clone();
+        if search.costs[&node] < cost {
+            continue;
+        }
+
+        if is_goal(&node) {
+            goal_cost = Some(cost);
+            search.goals.push(node);
+            continue;
+        }
+
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+
+            match search.costs.get(&next) {
+                Some(&known) if known < next_cost => {}
+                Some(&known) if known == next_cost => {
+                    search
+                        .predecessors
+                        .entry(next)
+                        .or_default()
+                        .push(node.clone());
+                }
+                _ => {
+                    search.costs.insert(next.clone(), next_cost);
+                    search.predecessors.insert(next.clone(), vec![node.clone()]);
+                    heap.push(Reverse((
+                        next_cost + heuristic(&next),
+                        next_cost,
+                        nodes.len(),
+                    )));
+                    nodes.push(next);
+                }
+            }
+        }
+    }
+
+    search
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{astar, bfs, dijkstra};
+    use crate::{Direction, Grid, Point};
+
+    const MAZE: &str = "#####\n#S..#\n#.#.#\n#..E#\n#####\n";
+
+    fn open_neighbours<'a>(
+        grid: &'a Grid<u8, &'a [u8]>,
+        pos: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        grid.neighbours(pos).filter(|&next| grid[next] != b'#')
+    }
+
+    #[test]
+    fn explores_grids() {
+        let grid = Grid::from_bytes(MAZE);
+        let (start, end) = (grid.find(&b'S').unwrap(), grid.find(&b'E').unwrap());
+
+        let search = bfs(start, |&pos| open_neighbours(&grid, pos));
+        assert_eq!(search.cost(&end), Some(4));
+        assert_eq!(search.costs().len(), 8);
+        assert_eq!(search.predecessors(&end).len(), 2);
+
+        let path = search.path(&end).unwrap();
+        assert_eq!(path.len(), 5);
+        assert_eq!((path[0], path[4]), (start, end));
+
+        assert_eq!(search.optimal_states([end]).len(), 8);
+        assert_eq!(search.cost(&(2, 2)), None);
+        assert_eq!(search.path(&(2, 2)), None);
+    }
+
+    #[test]
+    fn finds_cheapest_paths() {
+        let search = dijkstra(0_u32, |&n| {
+            [(n + 1, 5_u32), (n + 2, 1)]
+                .into_iter()
+                .filter(|&(next, _)| next <= 4)
+        });
+
+        assert_eq!(search.cost(&4), Some(2));
+        assert_eq!(search.cost(&3), Some(6));
+        assert_eq!(search.path(&4), Some(vec![0, 2, 4]));
+        assert_eq!(search.predecessors(&3), &[2, 1]);
+    }
+
+    #[test]
+    fn collects_tiles_of_all_optimal_paths() {
+        let grid = Grid::from_bytes(MAZE);
+        let (start, end) = (grid.find(&b'S').unwrap(), grid.find(&b'E').unwrap());
+
+        // moving costs 1 and turning costs 1000, like on day 16.
+        let search = astar(
+            (start, Direction::East),
+            |&(pos, direction)| {
+                let forward = Point::from(pos)
+                    .checked_add_direction(direction)
+                    .map(<(usize, usize)>::from)
+                    .filter(|&next| grid[next] != b'#')
+                    .map(|next| ((next, direction), 1));
+
+                forward.into_iter().chain([
+                    ((pos, direction.rotate_clockwise()), 1000),
+                    ((pos, direction.rotate_counterclockwise()), 1000),
+                ])
+            },
+            |&(pos, _)| Point::from(pos).manhattan(Point::from(end)),
+            |&(pos, _)| pos == end,
+        );
+
+        // only going east first avoids a second turn.
+        assert_eq!(search.goal_cost(), Some(1004));
+        assert_eq!(search.goals(), &[(end, Direction::South)]);
+        assert_eq!(
+            search.tiles(search.goals().to_vec(), |&(pos, _)| pos),
+            [(1, 1), (2, 1), (3, 1), (3, 2), (3, 3)]
+                .into_iter()
+                .collect()
+        );
+    }
+}
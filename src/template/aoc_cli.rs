@@ -1,10 +1,12 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
     fmt::Display,
+    fs,
+    path::Path,
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::{paths, Day, Year};
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -33,8 +35,8 @@ pub fn check() -> Result<(), AocCommandError> {
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+pub fn read(year: Option<Year>, day: Day) -> Result<Output, AocCommandError> {
+    let puzzle_path = paths::puzzle_path(Year::qualified(year), day);
 
     let args = build_args(
         "read",
@@ -43,15 +45,23 @@ pub fn read(day: Day) -> Result<Output, AocCommandError> {
             "--puzzle-file".into(),
             puzzle_path,
         ],
+        year,
         day,
     );
 
     call_aoc_cli(&args)
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+pub fn download(year: Option<Year>, day: Day) -> Result<Output, AocCommandError> {
+    let input_path = paths::input_path(Year::qualified(year), day);
+    let puzzle_path = paths::puzzle_path(Year::qualified(year), day);
+
+    // the data folders of a year are only created when scaffolding, which may happen after the download.
+    for path in [&input_path, &puzzle_path] {
+        if let Some(dir) = Path::new(path).parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+    }
 
     let args = build_args(
         "download",
@@ -62,6 +72,7 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
             "--puzzle-file".into(),
             puzzle_path.to_string(),
         ],
+        year,
         day,
     );
 
@@ -72,33 +83,23 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
     Ok(output)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+pub fn submit(
+    year: Option<Year>,
+    day: Day,
+    part: u8,
+    result: &str,
+) -> Result<Output, AocCommandError> {
     // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
+    let mut args = build_args("submit", &[], year, day);
     args.push(part.to_string());
     args.push(result.to_string());
     call_aoc_cli(&args)
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
-}
-
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
-}
-
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
-}
-
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
+fn build_args(command: &str, args: &[String], year: Option<Year>, day: Day) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
+    if let Some(year) = Year::or_default(year) {
         cmd_args.push("--year".into());
         cmd_args.push(year.to_string());
     }
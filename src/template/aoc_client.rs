@@ -0,0 +1,156 @@
+//! Minimal native HTTP client for the parts of the Advent of Code website that `aoc-cli` does not cover.
+
+use std::{env, fmt::Display, fs, io::Read};
+
+use crate::template::config;
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    Request(String),
+    Status(u16),
+    Body(String),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or write it to `~/.adventofcode.session`."
+            ),
+            AocClientError::Request(e) => write!(f, "request failed: {e}"),
+            AocClientError::Status(status) => {
+                write!(f, "server responded with status {status}.")
+            }
+            AocClientError::Body(e) => write!(f, "could not read response body: {e}"),
+        }
+    }
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.into(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(config::get().request_timeout)
+                .build(),
+        }
+    }
+
+    /// Creates a client for the live website, or for `AOC_BASE_URL` if set.
+    /// The session cookie is read from `AOC_SESSION` or the `~/.adventofcode.session` file used by `aoc-cli`.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let session = get_session().ok_or(AocClientError::SessionNotFound)?;
+        Ok(Self::new(&base_url, &session))
+    }
+
+    /// Performs an authenticated `GET` request for `path` and returns the response body.
+    pub fn get(&self, path: &str) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(&format!("{}{path}", self.base_url))
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .call();
+
+        let response = match response {
+            Ok(response) => response,
+            Err(ureq::Error::Status(status, _)) => return Err(AocClientError::Status(status)),
+            Err(e) => return Err(AocClientError::Request(e.to_string())),
+        };
+
+        let mut body = String::new();
+        response
+            .into_reader()
+            .read_to_string(&mut body)
+            .map_err(|e| AocClientError::Body(e.to_string()))?;
+
+        Ok(body)
+    }
+}
+
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session.trim().into());
+    }
+
+    let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+    let session = fs::read_to_string(format!("{home}/.adventofcode.session")).ok()?;
+    Some(session.trim().into())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AocClient, AocClientError};
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    /// Serves a single HTTP response on a random local port and returns the received request head.
+    fn serve_once(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut head = String::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+                head.push_str(&line);
+            }
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            head
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn sends_session_cookie() {
+        let (url, handle) = serve_once("200 OK", "{}");
+        let client = AocClient::new(&url, "abc");
+        assert_eq!(client.get("/2024/leaderboard").unwrap(), "{}");
+
+        let head = handle.join().unwrap();
+        assert!(head.starts_with("GET /2024/leaderboard HTTP/1.1"));
+        assert!(head.to_lowercase().contains("cookie: session=abc"));
+    }
+
+    #[test]
+    fn errors_on_bad_status() {
+        let (url, handle) = serve_once("404 Not Found", "");
+        let client = AocClient::new(&url, "abc");
+        assert!(matches!(
+            client.get("/missing"),
+            Err(AocClientError::Status(404))
+        ));
+        handle.join().unwrap();
+    }
+}
@@ -1,5 +1,12 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::{all_days, run_multi::run_multi, Profile, Year};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(year: Option<Year>, is_release: bool, profile: Option<&Profile>) {
+    run_multi(
+        year,
+        &all_days(year).collect(),
+        is_release,
+        false,
+        None,
+        profile,
+    );
 }
@@ -0,0 +1,92 @@
+use std::process;
+
+use crate::template::{
+    all_days, paths,
+    profiles::Answers,
+    run_multi::child_commands::{parse_results, run_solution},
+    Day, Profile, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+#[derive(Default)]
+struct Summary {
+    passed: usize,
+    failed: usize,
+    unknown: usize,
+}
+
+/// Run every solution against the input of every profile and compare the results with the profile's known answers.
+pub fn handle(year: Option<Year>, day: Option<Day>, is_release: bool) {
+    let profiles = Profile::all(year);
+
+    if profiles.is_empty() {
+        eprintln!(
+            "No input profiles found. Add inputs as \"{}\".",
+            paths::data_file(year, "inputs", "<profile>/DD.txt")
+        );
+        process::exit(1);
+    }
+
+    let days: Vec<Day> = day.map_or_else(|| all_days(year).collect(), |day| vec![day]);
+    let mut summary = Summary::default();
+
+    for day in days {
+        for profile in profiles.iter().filter(|p| p.has_input(year, day)) {
+            let answers = match Answers::read_from_file(year, profile) {
+                Ok(answers) => answers,
+                Err(e) => {
+                    eprintln!("Failed to read answers of profile \"{profile}\": {e}");
+                    process::exit(1);
+                }
+            };
+
+            println!("{ANSI_BOLD}Day {day}{ANSI_RESET} {ANSI_ITALIC}({profile}){ANSI_RESET}");
+            println!("------");
+
+            let output = match run_solution(year, day, false, is_release, None, Some(profile)) {
+                Ok(output) => output,
+                Err(e) => {
+                    println!("✖ Day {day} failed: {e}\n");
+                    summary.failed += 1;
+                    continue;
+                }
+            };
+
+            if output.is_empty() {
+                println!("Not solved.\n");
+                continue;
+            }
+
+            for (part, result) in parse_results(&output) {
+                let result = result.as_deref();
+                match answers.get(day, part) {
+                    Some(expected) if Some(expected) == result => {
+                        println!("✔ Part {part} matches the known answer.");
+                        summary.passed += 1;
+                    }
+                    Some(expected) => {
+                        println!(
+                            "✖ Part {part} returned {ANSI_BOLD}{}{ANSI_RESET}, expected {ANSI_BOLD}{expected}{ANSI_RESET}.",
+                            result.unwrap_or("nothing")
+                        );
+                        summary.failed += 1;
+                    }
+                    None => {
+                        println!("? Part {part} has no known answer.");
+                        summary.unknown += 1;
+                    }
+                }
+            }
+
+            println!();
+        }
+    }
+
+    println!(
+        "{ANSI_BOLD}Crosscheck:{ANSI_RESET} {} passed, {} failed, {} without known answer.",
+        summary.passed, summary.failed, summary.unknown
+    );
+
+    if summary.failed > 0 {
+        process::exit(1);
+    }
+}
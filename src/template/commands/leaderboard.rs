@@ -0,0 +1,81 @@
+use std::{
+    fs,
+    path::Path,
+    process,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::{
+    aoc_client::AocClient,
+    config,
+    leaderboard::{self, Leaderboard},
+    Year,
+};
+
+/// The Advent of Code maintainers ask that private leaderboards are not fetched more often than this.
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+fn get_cache_path(year: Year, id: u32) -> String {
+    format!("{}/leaderboards/{year}-{id}.json", config::get().data_dir)
+}
+
+/// Returns the cached leaderboard if it was fetched less than [`MIN_REFRESH_INTERVAL`] ago.
+fn read_fresh_cache(path: &str) -> Option<String> {
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    let age = SystemTime::now().duration_since(modified).ok()?;
+    if age < MIN_REFRESH_INTERVAL {
+        fs::read_to_string(path).ok()
+    } else {
+        None
+    }
+}
+
+fn fetch(year: Year, id: u32, cache_path: &str) -> Result<String, String> {
+    let client = AocClient::from_env().map_err(|e| e.to_string())?;
+    let json = client
+        .get(&format!("/{year}/leaderboard/private/view/{id}.json"))
+        .map_err(|e| e.to_string())?;
+
+    // validate before caching so a login page or error document never ends up in the cache.
+    Leaderboard::try_from(json.as_str())?;
+
+    if let Some(dir) = Path::new(cache_path).parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    fs::write(cache_path, &json).map_err(|e| e.to_string())?;
+
+    Ok(json)
+}
+
+pub fn handle(year: Option<Year>, id: u32) {
+    let Some(year) = Year::or_default(year) else {
+        eprintln!("`AOC_YEAR` is not set. Please set it in `.cargo/config.toml` or pass `--year`.");
+        process::exit(1);
+    };
+
+    let cache_path = get_cache_path(year, id);
+
+    let json = match read_fresh_cache(&cache_path) {
+        Some(json) => json,
+        None => match fetch(year, id, &cache_path) {
+            Ok(json) => json,
+            Err(e) => {
+                // fall back to a stale copy rather than failing outright.
+                let Ok(json) = fs::read_to_string(&cache_path) else {
+                    eprintln!("Failed to fetch leaderboard: {e}");
+                    process::exit(1);
+                };
+                eprintln!("Failed to fetch leaderboard, showing cached copy: {e}");
+                json
+            }
+        },
+    };
+
+    match Leaderboard::try_from(json.as_str()) {
+        Ok(board) => print!("{}", leaderboard::render(&board, id)),
+        Err(e) => {
+            eprintln!("Failed to parse leaderboard: {e}");
+            process::exit(1);
+        }
+    }
+}
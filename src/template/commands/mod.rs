@@ -1,6 +1,10 @@
 pub mod all;
+pub mod crosscheck;
 pub mod download;
+pub mod leaderboard;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
+#[cfg(feature = "today")]
+pub mod today;
@@ -1,15 +1,65 @@
-use std::process;
+use std::{
+    env, fs,
+    io::{stdout, IsTerminal, Write},
+    path::Path,
+    process::{self, Command, Stdio},
+};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{aoc_cli, markdown, paths, Day, Year};
 
-pub fn handle(day: Day) {
+pub fn handle(year: Option<Year>, day: Day) {
+    let puzzle_path = paths::puzzle_path(year, day);
+
+    // only hit the network when the puzzle has not been cached yet.
+    if !Path::new(&puzzle_path).exists() {
+        return read_remote(year, day);
+    }
+
+    match fs::read_to_string(&puzzle_path) {
+        Ok(puzzle) => page(&markdown::render(&puzzle)),
+        Err(e) => {
+            eprintln!("Failed to read puzzle file \"{puzzle_path}\": {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn read_remote(year: Option<Year>, day: Day) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::read(day) {
+    if let Err(e) = aoc_cli::read(year, day) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
 }
+
+/// Write `text` through the user's pager when attached to a terminal, falling back to plain stdout.
+/// `less -F` exits straight away when the text fits on one screen, so short puzzles are not paged.
+fn page(text: &str) {
+    if stdout().is_terminal() {
+        let pager = env::var("PAGER").unwrap_or_else(|_| "less -R -F -X".into());
+        let mut parts = pager.split_whitespace();
+
+        if let Some(program) = parts.next() {
+            let child = Command::new(program)
+                .args(parts)
+                .stdin(Stdio::piped())
+                .spawn();
+
+            if let Ok(mut child) = child {
+                if let Some(mut stdin) = child.stdin.take() {
+                    // the pager closing its input early (e.g. `q` in less) is not an error.
+                    let _ = stdin.write_all(text.as_bytes());
+                }
+                if child.wait().is_ok() {
+                    return;
+                }
+            }
+        }
+    }
+
+    print!("{text}");
+}
@@ -1,13 +1,17 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
+    path::Path,
     process,
 };
 
-use crate::template::Day;
-
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+use crate::template::{
+    config,
+    input_shape::InputShape,
+    paths,
+    templates::{self, Variables, DEFAULT_TEMPLATE},
+    Day, Year,
+};
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
@@ -19,18 +23,94 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Creates an empty file if it does not exist yet, e.g. an input that was downloaded before scaffolding.
+/// Returns whether the file was created.
+fn create_file(path: &str) -> Result<bool, std::io::Error> {
+    // data folders of other years may not exist yet.
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    match OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Renders the configured template, or one that matches the shape of a downloaded input.
+fn render_template(year: Option<Year>, day: Day, input_path: &str) -> String {
+    // a downloaded input tells which parser the solution needs.
+    let shape = fs::read_to_string(input_path)
+        .ok()
+        .and_then(|input| InputShape::detect(&input));
+
+    let template_name = match (&config::get().template, shape) {
+        (Some(name), _) => name.as_str(),
+        (None, Some(shape)) if templates::available().iter().any(|t| t == shape.template()) => {
+            println!(
+                "Detected {shape} input, using template \"{}\".",
+                shape.template()
+            );
+            shape.template()
+        }
+        (None, _) => DEFAULT_TEMPLATE,
+    };
+
+    let template = match templates::read(template_name) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to read module template: {e}");
+            process::exit(1);
+        }
+    };
+
+    let variables = Variables {
+        day,
+        year: Year::or_default(year),
+        title: templates::read_puzzle_title(&paths::puzzle_path(year, day)),
+        dimensions: shape.and_then(|shape| shape.dimensions()),
+    };
+
+    templates::render(&template, &variables)
+}
+
+/// Copies the solution of another day of the same year, see [`templates::from_existing`].
+fn copy_module(year: Option<Year>, from: Day, day: Day) -> String {
+    let source_path = paths::bin_path(year, from);
+
+    match fs::read_to_string(&source_path) {
+        Ok(source) => {
+            println!("Copying the solution of day {from} from \"{source_path}\".");
+            templates::from_existing(&source, day)
+        }
+        Err(e) => {
+            eprintln!("Failed to read solution \"{source_path}\": {e}");
+            process::exit(1);
+        }
+    }
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Scaffolds the module and data files of a day.
+/// With `from`, the module is copied from the solution of another day instead of a template.
+pub fn handle(year: Option<Year>, day: Day, overwrite: bool, from: Option<Day>) {
+    if let Some(event) = Year::or_default(year) {
+        if !event.has_day(day) {
+            eprintln!(
+                "The {event} event only has {} puzzles, day {day} does not exist.",
+                event.puzzle_count()
+            );
+            process::exit(1);
+        }
+    }
+
+    let input_path = paths::input_path(year, day);
+    let example_path = paths::example_path(year, day);
+    let module_path = paths::bin_path(year, day);
+
+    let module = match from {
+        Some(from) => copy_module(year, from, day),
+        None => render_template(year, day, &input_path),
+    };
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,11 +120,7 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(module.as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -55,9 +131,10 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     match create_file(&input_path) {
-        Ok(_) => {
+        Ok(true) => {
             println!("Created empty input file \"{}\"", &input_path);
         }
+        Ok(false) => {}
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
             process::exit(1);
@@ -65,9 +142,10 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     match create_file(&example_path) {
-        Ok(_) => {
+        Ok(true) => {
             println!("Created empty example file \"{}\"", &example_path);
         }
+        Ok(false) => {}
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
             process::exit(1);
@@ -75,5 +153,8 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    match year {
+        Some(year) => println!("🎄 Type `cargo solve {day} --year {year}` to run your solution."),
+        None => println!("🎄 Type `cargo solve {day}` to run your solution."),
+    }
 }
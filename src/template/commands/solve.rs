@@ -1,9 +1,25 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::visualize::VisualizeTarget;
+use crate::template::{config, paths, Day, InputSource, Profile, Year};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Option<Year>,
+    day: Day,
+    release: bool,
+    dhat: bool,
+    part: Option<u8>,
+    submit_part: Option<u8>,
+    profile: Option<&Profile>,
+    source: &InputSource,
+    visualize: Option<&VisualizeTarget>,
+) {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--bin".to_string(),
+        paths::bin_name(year, day),
+    ];
 
     if dhat {
         cmd_args.extend([
@@ -16,13 +32,35 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    if visualize.is_some() {
+        cmd_args.extend(["--features".to_string(), "visualize".to_string()]);
+    }
+
     cmd_args.push("--".to_string());
+    cmd_args.extend(config::get().forwarded_args());
+
+    if let Some(part) = part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
 
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
+    if let Some(profile) = profile {
+        cmd_args.push("--profile".to_string());
+        cmd_args.push(profile.to_string());
+    }
+
+    cmd_args.extend(source.to_args());
+
+    if let Some(visualize) = visualize {
+        cmd_args.push("--visualize".to_string());
+        cmd_args.push(visualize.to_string());
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
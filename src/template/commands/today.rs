@@ -0,0 +1,127 @@
+use std::{
+    cmp,
+    io::{stdout, Write},
+    process, thread,
+    time::Duration,
+};
+
+use chrono::{DateTime, Datelike, FixedOffset, Utc};
+
+use crate::template::{
+    aoc_cli,
+    commands::{read, scaffold},
+    Day, Year, ANSI_BOLD, ANSI_RESET,
+};
+
+const MAX_DOWNLOAD_ATTEMPTS: u32 = 10;
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+pub fn handle(wait: bool) {
+    let (year, day) = if wait {
+        wait_for_next_unlock()
+    } else {
+        match Day::today() {
+            Some(day) => (Year::new(u16::try_from(Utc::now().year()).unwrap()), day),
+            None => {
+                eprintln!(
+                    "`today` command can only be run while the puzzles of this year's \
+                    event unlock. Please use `scaffold` with a specific day \
+                    or `today --wait` to wait for the next puzzle."
+                );
+                process::exit(1)
+            }
+        }
+    };
+
+    // puzzles of an event other than the default year go to its year-qualified locations.
+    let year = Year::qualified(year);
+    download_with_retry(year, day);
+    scaffold::handle(year, day, false, None);
+    read::handle(year, day);
+}
+
+/// Block until the next puzzle unlocks, showing a countdown in the meantime.
+fn wait_for_next_unlock() -> (Option<Year>, Day) {
+    // fail early instead of after the countdown.
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        process::exit(1);
+    }
+
+    let Some((day, unlock)) = Day::next_unlock(Utc::now()) else {
+        eprintln!("There are no more puzzles to unlock this year.");
+        process::exit(1);
+    };
+
+    countdown(day, unlock);
+    (Year::new(u16::try_from(unlock.year()).unwrap()), day)
+}
+
+fn countdown(day: Day, unlock: DateTime<FixedOffset>) {
+    let mut stdout = stdout();
+
+    while let Ok(remaining) = (unlock.with_timezone(&Utc) - Utc::now()).to_std() {
+        if remaining.is_zero() {
+            break;
+        }
+
+        print!(
+            "\r⏳ Day {day} unlocks in {ANSI_BOLD}{}{ANSI_RESET} ",
+            format_countdown(remaining)
+        );
+        let _ = stdout.flush();
+
+        // wake up on the full second so the countdown ticks evenly.
+        let sub_second = Duration::from_nanos(u64::from(remaining.subsec_nanos()));
+        thread::sleep(if sub_second.is_zero() {
+            cmp::min(remaining, Duration::from_secs(1))
+        } else {
+            sub_second
+        });
+    }
+
+    println!("\r🎄 Day {day} is unlocked!                    ");
+}
+
+fn format_countdown(remaining: Duration) -> String {
+    let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    let (d, h, m, s) = (
+        secs / 86400,
+        secs % 86400 / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+    );
+    if d > 0 {
+        format!("{d}d {h:02}:{m:02}:{s:02}")
+    } else {
+        format!("{h:02}:{m:02}:{s:02}")
+    }
+}
+
+/// Inputs are sometimes published a few seconds after the puzzle unlocks, so retry with an exponential backoff.
+fn download_with_retry(year: Option<Year>, day: Day) {
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        process::exit(1);
+    }
+
+    let mut delay = Duration::from_secs(1);
+
+    for attempt in 1..=MAX_DOWNLOAD_ATTEMPTS {
+        match aoc_cli::download(year, day) {
+            Ok(_) => return,
+            Err(e) if attempt < MAX_DOWNLOAD_ATTEMPTS => {
+                eprintln!(
+                    "Download failed ({e}), retrying in {}s ({attempt}/{MAX_DOWNLOAD_ATTEMPTS})...",
+                    delay.as_secs()
+                );
+                thread::sleep(delay);
+                delay = cmp::min(delay * 2, MAX_RETRY_DELAY);
+            }
+            Err(e) => {
+                eprintln!("failed to call aoc-cli: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
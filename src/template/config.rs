@@ -0,0 +1,374 @@
+//! Project configuration, read once from `aoc.toml` in the project root.
+
+use std::{collections::HashMap, env, fs, process, sync::OnceLock, time::Duration};
+
+use toml::Table;
+
+use crate::template::{Year, MAX_DAY};
+
+const CONFIG_FILE_PATH: &str = "aoc.toml";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// Default year, takes precedence over `AOC_YEAR`.
+    pub year: Option<Year>,
+    /// Root of the inputs, examples, puzzles and timings.
+    pub data_dir: String,
+    /// Name of the module template used by `scaffold`, see the `templates/` directory.
+    /// If not set, the template is picked by the shape of the input.
+    pub template: Option<String>,
+    /// Whether `solve`, `all` and `crosscheck` build solutions in release mode. If not set, `solve` and `crosscheck` do
+    /// and `all` does not.
+    /// `--release` and `--debug` override it.
+    pub release: Option<bool>,
+    /// Time spent benchmarking each part.
+    pub bench_budget: Duration,
+    /// Solutions are aborted after this time.
+    pub solution_timeout: Option<Duration>,
+    /// Timeout of requests to the Advent of Code website.
+    pub request_timeout: Duration,
+    /// Files that contain benchmark tables.
+    pub readme_files: Vec<String>,
+    /// Marker around the benchmark table of the default year.
+    pub readme_marker: String,
+    /// Number of puzzles of events, see [`Year::puzzle_count`] for the default.
+    pub puzzle_counts: HashMap<Year, u8>,
+    overrides: Overrides,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: None,
+            data_dir: "data".into(),
+            template: None,
+            release: None,
+            bench_budget: Duration::from_secs(1),
+            solution_timeout: None,
+            request_timeout: Duration::from_secs(10),
+            readme_files: vec!["README.md".into()],
+            readme_marker: "<!--- benchmarking table --->".into(),
+            puzzle_counts: HashMap::new(),
+            overrides: Overrides::default(),
+        }
+    }
+}
+
+/// Settings passed as command-line flags. These take precedence over `aoc.toml`.
+#[derive(Clone, Debug, Default)]
+pub struct Overrides {
+    pub data_dir: Option<String>,
+    pub template: Option<String>,
+    pub bench_budget_ms: Option<u64>,
+    pub timeout_secs: Option<u64>,
+}
+
+impl Overrides {
+    /// Reads the overrides that were forwarded to a solution binary, see [`Overrides::to_args`].
+    fn from_args(args: &[String]) -> Self {
+        let value_of = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .and_then(|i| args.get(i + 1))
+                .cloned()
+        };
+
+        Self {
+            data_dir: value_of("--data-dir"),
+            template: None,
+            bench_budget_ms: value_of("--budget").and_then(|x| x.parse().ok()),
+            timeout_secs: value_of("--timeout").and_then(|x| x.parse().ok()),
+        }
+    }
+
+    /// Arguments that forward the overrides relevant to solutions to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(data_dir) = &self.data_dir {
+            args.extend(["--data-dir".into(), data_dir.clone()]);
+        }
+        if let Some(budget) = self.bench_budget_ms {
+            args.extend(["--budget".into(), budget.to_string()]);
+        }
+        if let Some(timeout) = self.timeout_secs {
+            args.extend(["--timeout".into(), timeout.to_string()]);
+        }
+
+        args
+    }
+}
+
+impl Config {
+    /// Reads `aoc.toml`. If not present, returns the default configuration.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(CONFIG_FILE_PATH) {
+            Ok(toml) => Config::try_from(toml.as_str()),
+            Err(_) => Ok(Config::default()),
+        }
+    }
+
+    #[must_use]
+    pub fn with_overrides(mut self, overrides: Overrides) -> Self {
+        if let Some(data_dir) = &overrides.data_dir {
+            self.data_dir.clone_from(data_dir);
+        }
+        if let Some(template) = &overrides.template {
+            self.template = Some(template.clone());
+        }
+        if let Some(budget) = overrides.bench_budget_ms {
+            self.bench_budget = Duration::from_millis(budget);
+        }
+        if let Some(timeout) = overrides.timeout_secs {
+            // `--timeout 0` disables a configured timeout.
+            self.solution_timeout = (timeout > 0).then(|| Duration::from_secs(timeout));
+        }
+        self.overrides = overrides;
+        self
+    }
+
+    /// Arguments to forward the command-line overrides to solution binaries.
+    pub fn forwarded_args(&self) -> Vec<String> {
+        self.overrides.to_args()
+    }
+}
+
+/// Initializes the configuration with the command-line overrides. Has no effect if it was already read.
+pub fn init(overrides: Overrides) {
+    let _ = CONFIG.set(load(overrides));
+}
+
+/// Returns the configuration, reading it on first use.
+/// Solution binaries pick up the overrides forwarded to them on the command line.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| {
+        let args: Vec<String> = env::args().collect();
+        load(Overrides::from_args(&args))
+    })
+}
+
+fn load(overrides: Overrides) -> Config {
+    match Config::read_from_file() {
+        Ok(config) => config.with_overrides(overrides),
+        Err(e) => {
+            eprintln!("Failed to read \"{CONFIG_FILE_PATH}\": {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<&str> for Config {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let toml: Table = value.parse().map_err(|e| format!("not valid TOML: {e}"))?;
+        let mut config = Config::default();
+
+        if let Some(year) = toml.get("year") {
+            let year = year
+                .as_integer()
+                .and_then(|x| u16::try_from(x).ok())
+                .and_then(Year::new)
+                .ok_or("expected `year` to be a year from 2015 onwards.")?;
+            config.year = Some(year);
+        }
+
+        if let Some(data_dir) = get_string(&toml, "data_dir")? {
+            config.data_dir = data_dir.trim_end_matches('/').into();
+        }
+
+        config.template = get_string(&toml, "template")?;
+
+        if let Some(runner) = get_table(&toml, "runner")? {
+            if let Some(release) = runner.get("release") {
+                config.release = Some(
+                    release
+                        .as_bool()
+                        .ok_or("expected `runner.release` to be a boolean.")?,
+                );
+            }
+        }
+
+        if let Some(benchmarks) = get_table(&toml, "benchmarks")? {
+            if let Some(budget) = get_number(benchmarks, "benchmarks.budget_ms")? {
+                config.bench_budget = Duration::from_millis(budget);
+            }
+        }
+
+        if let Some(timeouts) = get_table(&toml, "timeouts")? {
+            if let Some(solution) = get_number(timeouts, "timeouts.solution")? {
+                config.solution_timeout = (solution > 0).then(|| Duration::from_secs(solution));
+            }
+            if let Some(request) = get_number(timeouts, "timeouts.request")? {
+                config.request_timeout = Duration::from_secs(request);
+            }
+        }
+
+        if let Some(readme) = get_table(&toml, "readme")? {
+            if let Some(files) = readme.get("files") {
+                config.readme_files = files
+                    .as_array()
+                    .and_then(|files| files.iter().map(|f| f.as_str().map(String::from)).collect())
+                    .ok_or("expected `readme.files` to be an array of strings.")?;
+            }
+            if let Some(marker) = get_string(readme, "marker")? {
+                config.readme_marker = marker;
+            }
+        }
+
+        if let Some(events) = get_table(&toml, "events")? {
+            for (year, count) in events {
+                let year = year.parse().ok().and_then(Year::new).ok_or(format!(
+                    "expected `events.{year}` to be a year from 2015 onwards."
+                ))?;
+                let count = count
+                    .as_integer()
+                    .and_then(|x| u8::try_from(x).ok())
+                    .filter(|x| (1..=MAX_DAY).contains(x))
+                    .ok_or(format!(
+                        "expected `events.{year}` to be a number of puzzles from 1 to {MAX_DAY}."
+                    ))?;
+                config.puzzle_counts.insert(year, count);
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+fn get_string(table: &Table, key: &str) -> Result<Option<String>, String> {
+    table
+        .get(key)
+        .map(|v| {
+            v.as_str()
+                .map(String::from)
+                .ok_or(format!("expected `{key}` to be a string."))
+        })
+        .transpose()
+}
+
+fn get_table<'a>(table: &'a Table, key: &str) -> Result<Option<&'a Table>, String> {
+    table
+        .get(key)
+        .map(|v| {
+            v.as_table()
+                .ok_or(format!("expected `[{key}]` to be a table."))
+        })
+        .transpose()
+}
+
+/// Reads a non-negative integer, `path` is the full name of the key for error messages.
+fn get_number(table: &Table, path: &str) -> Result<Option<u64>, String> {
+    let key = path.rsplit('.').next().unwrap_or(path);
+    table
+        .get(key)
+        .map(|v| {
+            v.as_integer()
+                .and_then(|x| u64::try_from(x).ok())
+                .ok_or(format!("expected `{path}` to be a non-negative integer."))
+        })
+        .transpose()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Config, Overrides};
+    use crate::template::Year;
+
+    #[test]
+    fn uses_defaults_for_empty_files() {
+        let config = Config::try_from("").unwrap();
+        assert_eq!(config.data_dir, "data");
+        assert_eq!(config.year, None);
+        assert_eq!(config.bench_budget, Duration::from_secs(1));
+        assert_eq!(config.readme_files, vec!["README.md".to_string()]);
+    }
+
+    #[test]
+    fn parses_config() {
+        let config = Config::try_from(
+            r#"
+            year = 2023
+            data_dir = "aoc-data/"
+            template = "templates/custom.txt"
+
+            [runner]
+            release = false
+
+            [benchmarks]
+            budget_ms = 250
+
+            [timeouts]
+            solution = 30
+            request = 5
+
+            [readme]
+            files = ["README.md", "docs/benchmarks.md"]
+            marker = "<!-- bench -->"
+
+            [events]
+            2026 = 10
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Year::new(2023));
+        assert_eq!(config.data_dir, "aoc-data");
+        assert_eq!(config.template.as_deref(), Some("templates/custom.txt"));
+        assert_eq!(config.release, Some(false));
+        assert_eq!(config.bench_budget, Duration::from_millis(250));
+        assert_eq!(config.solution_timeout, Some(Duration::from_secs(30)));
+        assert_eq!(config.request_timeout, Duration::from_secs(5));
+        assert_eq!(config.readme_files.len(), 2);
+        assert_eq!(config.readme_marker, "<!-- bench -->");
+        assert_eq!(config.puzzle_counts[&Year::new(2026).unwrap()], 10);
+    }
+
+    #[test]
+    fn errors_for_invalid_values() {
+        assert!(Config::try_from("year = 2000").is_err());
+        assert!(Config::try_from("data_dir = 1").is_err());
+        assert!(Config::try_from("[timeouts]\nsolution = -1").is_err());
+        assert!(Config::try_from("readme = 1").is_err());
+        assert!(Config::try_from("[events]\n2026 = 30").is_err());
+        assert!(Config::try_from("[events]\nnext = 12").is_err());
+    }
+
+    #[test]
+    fn applies_overrides() {
+        let config = Config::try_from("[timeouts]\nsolution = 30")
+            .unwrap()
+            .with_overrides(Overrides {
+                data_dir: Some("other".into()),
+                timeout_secs: Some(0),
+                ..Overrides::default()
+            });
+
+        assert_eq!(config.data_dir, "other");
+        assert_eq!(config.solution_timeout, None);
+        assert_eq!(
+            config.forwarded_args(),
+            vec!["--data-dir", "other", "--timeout", "0"]
+        );
+    }
+
+    #[test]
+    fn reads_forwarded_overrides() {
+        let args: Vec<String> = ["02", "--time", "--budget", "100", "--data-dir", "x"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let overrides = Overrides::from_args(&args);
+        assert_eq!(overrides.data_dir.as_deref(), Some("x"));
+        assert_eq!(overrides.bench_budget_ms, Some(100));
+        assert_eq!(overrides.timeout_secs, None);
+    }
+}
@@ -3,12 +3,18 @@ use std::fmt::Display;
 use std::str::FromStr;
 
 #[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
+
+use crate::template::Year;
 
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
 
+/// The last day of the longest events. See [`Year::puzzle_count`] for the length of an event.
+pub const MAX_DAY: u8 = 25;
+
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// Not every event has 25 puzzles, see [`Year::has_day`].
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -25,7 +31,7 @@ impl Day {
     /// Creates a [`Day`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        if day == 0 || day > MAX_DAY {
             return None;
         }
         Some(Self(day))
@@ -45,16 +51,37 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if a puzzle of this year's event unlocked today, `None` otherwise.
     pub fn today() -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
+        let year = Year::new(u16::try_from(today.year()).ok()?)?;
+        if today.month() == 12 && today.day() <= u32::from(year.puzzle_count()) {
             Self::new(u8::try_from(today.day()).ok()?)
         } else {
             None
         }
     }
+
+    /// Returns the next day to unlock after `now`, together with the instant it unlocks.
+    /// Puzzles unlock at midnight server time, `None` is returned once the last puzzle of the event is out.
+    pub fn next_unlock(now: DateTime<Utc>) -> Option<(Self, DateTime<FixedOffset>)> {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+        let now = now.with_timezone(&offset);
+        let last_day = Year::new(u16::try_from(now.year()).ok()?)?.puzzle_count();
+
+        let day = match now.month() {
+            12 if now.day() >= u32::from(last_day) => return None,
+            12 => now.day() + 1,
+            _ => 1,
+        };
+
+        let unlock = offset
+            .with_ymd_and_hms(now.year(), 12, day, 0, 0, 0)
+            .single()?;
+
+        Some((Self::new(u8::try_from(day).ok()?)?, unlock))
+    }
 }
 
 impl Display for Day {
@@ -100,20 +127,23 @@ impl Display for DayFromStrError {
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
-pub fn all_days() -> AllDays {
-    AllDays::new()
+/// An iterator that yields every day of the event of a year, see [`Year::puzzle_count_of`].
+pub fn all_days(year: Option<Year>) -> AllDays {
+    AllDays::new(Year::puzzle_count_of(year))
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent from the 1st to the last day of an event.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self {
-        Self { current: 1 }
+    pub fn new(last: u8) -> Self {
+        Self {
+            current: 1,
+            last: last.min(MAX_DAY),
+        }
     }
 }
 
@@ -121,10 +151,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above `MAX_DAY`.
         let day = Day(self.current);
         self.current += 1;
 
@@ -154,11 +184,70 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, AllDays, Day};
+    use crate::template::Year;
+
+    #[cfg(feature = "today")]
+    mod next_unlock {
+        use super::Day;
+        use chrono::{DateTime, Utc};
+
+        fn unlock_at(now: &str) -> Option<(Day, String)> {
+            let now = DateTime::parse_from_rfc3339(now)
+                .unwrap()
+                .with_timezone(&Utc);
+            Day::next_unlock(now).map(|(day, unlock)| (day, unlock.to_rfc3339()))
+        }
+
+        #[test]
+        fn handles_moments_before_first_unlock() {
+            assert_eq!(
+                unlock_at("2024-12-01T04:59:30+00:00"),
+                Some((Day(1), "2024-12-01T00:00:00-05:00".into()))
+            );
+        }
+
+        #[test]
+        fn handles_days_before_december() {
+            assert_eq!(
+                unlock_at("2024-10-19T12:00:00+00:00"),
+                Some((Day(1), "2024-12-01T00:00:00-05:00".into()))
+            );
+        }
+
+        #[test]
+        fn handles_unlocked_days() {
+            // 06:00 UTC is 01:00 server time, so day 5 is unlocked and day 6 is next.
+            assert_eq!(
+                unlock_at("2024-12-05T06:00:00+00:00"),
+                Some((Day(6), "2024-12-06T00:00:00-05:00".into()))
+            );
+            // 03:00 UTC on the 6th is still the 5th in server time.
+            assert_eq!(
+                unlock_at("2024-12-06T03:00:00+00:00"),
+                Some((Day(6), "2024-12-06T00:00:00-05:00".into()))
+            );
+        }
+
+        #[test]
+        fn handles_end_of_event() {
+            assert_eq!(unlock_at("2024-12-25T06:00:00+00:00"), None);
+            assert_eq!(unlock_at("2024-12-31T06:00:00+00:00"), None);
+        }
+
+        #[test]
+        fn handles_short_events() {
+            assert_eq!(
+                unlock_at("2025-12-11T06:00:00+00:00"),
+                Some((Day(12), "2025-12-12T00:00:00-05:00".into()))
+            );
+            assert_eq!(unlock_at("2025-12-12T06:00:00+00:00"), None);
+        }
+    }
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = AllDays::new(25);
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -187,6 +276,12 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn all_days_of_event() {
+        assert_eq!(all_days(Year::new(2024)).count(), 25);
+        assert_eq!(all_days(Year::new(2025)).last(), Some(Day(12)));
+    }
 }
 
 /* -------------------------------------------------------------------------- */
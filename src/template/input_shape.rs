@@ -0,0 +1,147 @@
+//! Detects the shape of a puzzle input, so `scaffold` can pick a template with a matching parser.
+
+use std::fmt::Display;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputShape {
+    /// A rectangular grid of characters.
+    Grid { width: usize, height: usize },
+    /// Lines that each contain one or more integers.
+    IntegerLines,
+    /// Blocks separated by blank lines.
+    Sections,
+    /// A single line of comma-separated values.
+    CommaList,
+}
+
+impl InputShape {
+    /// Detects the shape of an input. Returns `None` if the input is empty or has no recognizable shape.
+    pub fn detect(input: &str) -> Option<Self> {
+        let input = input.trim_end_matches(['\n', '\r']);
+        if input.trim().is_empty() {
+            return None;
+        }
+
+        if input.contains("\n\n") || input.contains("\r\n\r\n") {
+            return Some(Self::Sections);
+        }
+
+        let lines: Vec<&str> = input.lines().collect();
+
+        if lines.len() == 1 && lines[0].contains(',') {
+            return Some(Self::CommaList);
+        }
+
+        let width = lines[0].len();
+        let is_grid = lines.len() > 1
+            && width > 1
+            && lines
+                .iter()
+                .all(|line| line.len() == width && !line.contains([' ', ',']));
+
+        if is_grid {
+            return Some(Self::Grid {
+                width,
+                height: lines.len(),
+            });
+        }
+
+        if lines
+            .iter()
+            .all(|line| line.bytes().any(|b| b.is_ascii_digit()))
+        {
+            return Some(Self::IntegerLines);
+        }
+
+        None
+    }
+
+    /// Name of the template with a parser for this shape, see the `templates/` directory.
+    pub fn template(&self) -> &'static str {
+        match self {
+            Self::Grid { .. } => "grid",
+            Self::IntegerLines => "lines",
+            Self::Sections => "sections",
+            Self::CommaList => "comma",
+        }
+    }
+
+    /// Dimensions of grid inputs.
+    pub fn dimensions(&self) -> Option<(usize, usize)> {
+        match self {
+            Self::Grid { width, height } => Some((*width, *height)),
+            _ => None,
+        }
+    }
+}
+
+impl Display for InputShape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Grid { width, height } => write!(f, "{width}x{height} grid"),
+            Self::IntegerLines => f.write_str("lines of integers"),
+            Self::Sections => f.write_str("sections"),
+            Self::CommaList => f.write_str("comma-separated list"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::InputShape;
+
+    #[test]
+    fn detects_grids() {
+        assert_eq!(
+            InputShape::detect("89010123\n78121874\n87430965\n"),
+            Some(InputShape::Grid {
+                width: 8,
+                height: 3
+            })
+        );
+        assert_eq!(
+            InputShape::detect("....#\n.#...\n"),
+            Some(InputShape::Grid {
+                width: 5,
+                height: 2
+            })
+        );
+    }
+
+    #[test]
+    fn detects_integer_lines() {
+        assert_eq!(
+            InputShape::detect("190: 10 19\n3267: 81 40 27\n"),
+            Some(InputShape::IntegerLines)
+        );
+        assert_eq!(
+            InputShape::detect("3   4\n4   3\n2   5\n"),
+            Some(InputShape::IntegerLines)
+        );
+    }
+
+    #[test]
+    fn detects_sections() {
+        assert_eq!(
+            InputShape::detect("r, wr, b\n\nbrwrr\nbggr\n"),
+            Some(InputShape::Sections)
+        );
+    }
+
+    #[test]
+    fn detects_comma_lists() {
+        assert_eq!(
+            InputShape::detect("rn=1,cm-,qp=3\n"),
+            Some(InputShape::CommaList)
+        );
+    }
+
+    #[test]
+    fn handles_unknown_shapes() {
+        assert_eq!(InputShape::detect(""), None);
+        assert_eq!(InputShape::detect("\n"), None);
+        assert_eq!(InputShape::detect("abc def\nghi\n"), None);
+    }
+}
@@ -0,0 +1,141 @@
+//! Alternate inputs of a solution, e.g. to debug on an example in release mode with `cargo solve 5 --example`.
+
+use std::{env, fs, io};
+
+use crate::template::{read_file, read_file_part, Day, Params};
+
+/// Where a solution reads its input from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input, or the input of the profile passed with `--profile`.
+    #[default]
+    Puzzle,
+    /// The example, `--example`.
+    Example,
+    /// The example with a part suffix, e.g. `examples/05-2.txt` for `--example-part 2`.
+    ExamplePart(u8),
+    /// A file, `--input path/to/file`.
+    File(String),
+    /// Standard input, `--input -`.
+    Stdin,
+}
+
+impl InputSource {
+    /// Combines the flags of `cargo solve`. At most one of them may be set.
+    pub fn from_flags(
+        example: bool,
+        example_part: Option<u8>,
+        input: Option<String>,
+    ) -> Result<Self, String> {
+        match (example, example_part, input) {
+            (false, None, None) => Ok(Self::Puzzle),
+            (true, None, None) => Ok(Self::Example),
+            (false, Some(part), None) => Ok(Self::ExamplePart(part)),
+            (false, None, Some(input)) if input == "-" => Ok(Self::Stdin),
+            (false, None, Some(input)) => Ok(Self::File(input)),
+            _ => Err("only one of `--example`, `--example-part` and `--input` can be used.".into()),
+        }
+    }
+
+    /// Returns the source a solution binary was started with, see [`InputSource::to_args`].
+    pub fn current() -> Self {
+        let args: Vec<String> = env::args().collect();
+        Self::from_args(&args)
+    }
+
+    fn from_args(args: &[String]) -> Self {
+        let value_of = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .and_then(|i| args.get(i + 1))
+                .cloned()
+        };
+
+        let example_part = value_of("--example-part").and_then(|x| x.parse().ok());
+        let example = args.iter().any(|x| x == "--example");
+
+        Self::from_flags(example, example_part, value_of("--input")).unwrap_or_default()
+    }
+
+    /// Arguments that forward the source to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Puzzle => vec![],
+            Self::Example => vec!["--example".into()],
+            Self::ExamplePart(part) => vec!["--example-part".into(), part.to_string()],
+            Self::File(path) => vec!["--input".into(), path.clone()],
+            Self::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+
+    /// Examples are solved with [`Params::Example`], all other inputs with [`Params::Input`].
+    pub fn params(&self) -> Params {
+        match self {
+            Self::Example | Self::ExamplePart(_) => Params::Example,
+            _ => Params::Input,
+        }
+    }
+
+    /// Reads the input. Returns `None` for [`InputSource::Puzzle`], which depends on the profile.
+    pub(crate) fn read(&self, day: Day) -> Option<String> {
+        match self {
+            Self::Puzzle => None,
+            Self::Example => Some(read_file("examples", day)),
+            Self::ExamplePart(part) => Some(read_file_part("examples", day, *part)),
+            Self::File(path) => Some(fs::read_to_string(path).expect("could not open input file")),
+            Self::Stdin => Some(io::read_to_string(io::stdin()).expect("could not read stdin")),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::InputSource;
+    use crate::template::Params;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn combines_flags() {
+        assert_eq!(
+            InputSource::from_flags(false, None, None),
+            Ok(InputSource::Puzzle)
+        );
+        assert_eq!(
+            InputSource::from_flags(false, None, Some("-".into())),
+            Ok(InputSource::Stdin)
+        );
+        assert_eq!(
+            InputSource::from_flags(false, None, Some("in.txt".into())),
+            Ok(InputSource::File("in.txt".into()))
+        );
+        assert!(InputSource::from_flags(true, Some(2), None).is_err());
+        assert!(InputSource::from_flags(true, None, Some("-".into())).is_err());
+    }
+
+    #[test]
+    fn roundtrips_args() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::Example,
+            InputSource::ExamplePart(2),
+            InputSource::File("data/other.txt".into()),
+            InputSource::Stdin,
+        ] {
+            let mut forwarded = args(&["05", "--time"]);
+            forwarded.extend(source.to_args());
+            assert_eq!(InputSource::from_args(&forwarded), source);
+        }
+    }
+
+    #[test]
+    fn solves_examples_with_example_params() {
+        assert_eq!(InputSource::Example.params(), Params::Example);
+        assert_eq!(InputSource::ExamplePart(2).params(), Params::Example);
+        assert_eq!(InputSource::Stdin.params(), Params::Input);
+    }
+}
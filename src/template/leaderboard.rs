@@ -0,0 +1,300 @@
+//! Parsing and terminal rendering of private leaderboards.
+
+use std::{collections::HashMap, fmt::Write, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, MAX_DAY};
+
+/// Completion timestamps (seconds since the epoch) of both parts of a day.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DayProgress {
+    pub part_1: Option<i64>,
+    pub part_2: Option<i64>,
+}
+
+impl DayProgress {
+    /// Seconds between getting the first and the second star.
+    pub fn delta(&self) -> Option<i64> {
+        Some(self.part_2? - self.part_1?)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub local_score: u64,
+    pub last_star_ts: i64,
+    pub days: HashMap<u8, DayProgress>,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Leaderboard {
+    pub event: String,
+    /// Members ordered by rank.
+    pub members: Vec<Member>,
+}
+
+impl TryFrom<&str> for Leaderboard {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected leaderboard to be an object.")?;
+
+        let event = json
+            .get("event")
+            .and_then(|v| v.get::<String>())
+            .cloned()
+            .ok_or("expected leaderboard.event to be a string.")?;
+
+        let mut members = json
+            .get("members")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("expected leaderboard.members to be an object.")?
+            .values()
+            .map(Member::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(a.last_star_ts.cmp(&b.last_star_ts))
+                .then(a.id.cmp(&b.id))
+        });
+
+        Ok(Leaderboard { event, members })
+    }
+}
+
+impl TryFrom<&JsonValue> for Member {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected member to be an object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("expected member.{key} to be a number."))
+        };
+
+        let name = json.get("name").and_then(|v| v.get::<String>()).cloned();
+
+        let mut days = HashMap::new();
+        if let Some(completion) = json
+            .get("completion_day_level")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+        {
+            for (day, parts) in completion {
+                let day: u8 = day
+                    .parse()
+                    .map_err(|_| format!("expected `{day}` to be a day number."))?;
+                let parts = parts
+                    .get::<HashMap<String, JsonValue>>()
+                    .ok_or("expected completion_day_level entries to be objects.")?;
+
+                let get_star_ts = |part: &str| {
+                    parts
+                        .get(part)
+                        .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+                        .and_then(|v| v.get("get_star_ts"))
+                        .and_then(|v| v.get::<f64>())
+                        .map(|ts| *ts as i64)
+                };
+
+                days.insert(
+                    day,
+                    DayProgress {
+                        part_1: get_star_ts("1"),
+                        part_2: get_star_ts("2"),
+                    },
+                );
+            }
+        }
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Member {
+            id: number("id")? as u64,
+            name,
+            local_score: number("local_score")? as u64,
+            last_star_ts: number("last_star_ts").unwrap_or(0.0) as i64,
+            days,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn format_delta(seconds: i64) -> String {
+    let (h, m, s) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+    if h > 0 {
+        format!("{h}:{m:02}:{s:02}")
+    } else {
+        format!("{m}:{s:02}")
+    }
+}
+
+fn format_stars(member: &Member, days: u8) -> String {
+    (1..=days)
+        .map(|day| match member.days.get(&day) {
+            Some(DayProgress {
+                part_2: Some(_), ..
+            }) => format!("{ANSI_BOLD}*{ANSI_RESET}"),
+            Some(DayProgress {
+                part_1: Some(_), ..
+            }) => "+".into(),
+            _ => ".".into(),
+        })
+        .collect()
+}
+
+/// Renders the rankings with a star chart per day, followed by every member's part two deltas.
+/// Gold stars are shown as a bold `*`, days with only the first star as `+`.
+pub fn render(leaderboard: &Leaderboard, id: u32) -> String {
+    let mut out = String::new();
+    let rank_width = leaderboard.members.len().to_string().len() + 1;
+    let score_width = leaderboard
+        .members
+        .first()
+        .map_or(1, |m| m.local_score.to_string().len());
+    let indent = " ".repeat(rank_width + score_width + 3);
+
+    let _ = writeln!(
+        out,
+        "{ANSI_BOLD}Private leaderboard {id} ({}){ANSI_RESET}\n",
+        leaderboard.event
+    );
+
+    let days = leaderboard
+        .event
+        .parse::<Year>()
+        .map_or(MAX_DAY, Year::puzzle_count);
+
+    let tens: String = (1..=days)
+        .map(|d| {
+            if d < 10 {
+                ' '
+            } else {
+                char::from(b'0' + d / 10)
+            }
+        })
+        .collect();
+    let ones: String = (1..=days).map(|d| char::from(b'0' + d % 10)).collect();
+    let _ = writeln!(out, "{indent}{}", tens.trim_end());
+    let _ = writeln!(out, "{indent}{ones}");
+
+    for (i, member) in leaderboard.members.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "{:>rank_width$}) {:>score_width$} {} {}",
+            i + 1,
+            member.local_score,
+            format_stars(member, days),
+            member.display_name()
+        );
+    }
+
+    let _ = writeln!(out, "\n{ANSI_BOLD}Part two deltas{ANSI_RESET}");
+
+    for member in &leaderboard.members {
+        let mut deltas: Vec<(u8, i64)> = member
+            .days
+            .iter()
+            .filter_map(|(day, progress)| Some((*day, progress.delta()?)))
+            .collect();
+        deltas.sort_unstable();
+
+        let deltas = if deltas.is_empty() {
+            format!("{ANSI_ITALIC}none{ANSI_RESET}")
+        } else {
+            deltas
+                .iter()
+                .map(|(day, delta)| format!("d{day} {}", format_delta(*delta)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let _ = writeln!(out, "{}: {deltas}", member.display_name());
+    }
+
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_delta, render, Leaderboard};
+
+    const JSON: &str = r#"{
+        "event": "2024",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1, "name": "Alice", "stars": 3, "local_score": 10, "global_score": 0, "last_star_ts": 1733100000,
+                "completion_day_level": {
+                    "1": { "1": { "get_star_ts": 1733030000, "star_index": 1 }, "2": { "get_star_ts": 1733030083, "star_index": 2 } },
+                    "2": { "1": { "get_star_ts": 1733100000, "star_index": 3 } }
+                }
+            },
+            "2": {
+                "id": 2, "name": null, "stars": 2, "local_score": 12, "global_score": 0, "last_star_ts": 1733040000,
+                "completion_day_level": {
+                    "1": { "1": { "get_star_ts": 1733030100, "star_index": 4 }, "2": { "get_star_ts": 1733040000, "star_index": 5 } }
+                }
+            }
+        }
+    }"#;
+
+    #[test]
+    fn parses_leaderboard() {
+        let leaderboard = Leaderboard::try_from(JSON).unwrap();
+        assert_eq!(leaderboard.event, "2024");
+        assert_eq!(leaderboard.members.len(), 2);
+        // ranked by local score.
+        assert_eq!(leaderboard.members[0].id, 2);
+        assert_eq!(leaderboard.members[1].name, Some("Alice".into()));
+    }
+
+    #[test]
+    fn computes_deltas() {
+        let leaderboard = Leaderboard::try_from(JSON).unwrap();
+        let alice = &leaderboard.members[1];
+        assert_eq!(alice.days[&1].delta(), Some(83));
+        assert_eq!(alice.days[&2].delta(), None);
+    }
+
+    #[test]
+    fn renders_rankings_and_deltas() {
+        let leaderboard = Leaderboard::try_from(JSON).unwrap();
+        let s = render(&leaderboard, 42);
+        assert!(s.contains("Private leaderboard 42 (2024)"));
+        assert!(s.contains("1) 12 "));
+        assert!(s.contains("(anonymous user #2)"));
+        assert!(s.contains("Alice: d1 1:23"));
+        assert!(s.contains("(anonymous user #2): d1 2:45:00"));
+    }
+
+    #[test]
+    fn formats_deltas() {
+        assert_eq!(format_delta(5), "0:05");
+        assert_eq!(format_delta(3725), "1:02:05");
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_leaderboard() {
+        Leaderboard::try_from(r#"{ "members": {} }"#).unwrap();
+    }
+}
@@ -0,0 +1,261 @@
+//! Renders the markdown puzzle descriptions written by `aoc-cli` for display in a terminal.
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const CODE_INDENT: &str = "    ";
+
+/// Render a markdown document to a string containing ANSI escape sequences.
+#[must_use]
+pub fn render(markdown: &str) -> String {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut output: Vec<String> = Vec::with_capacity(lines.len());
+
+    let mut in_code_block = false;
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        i += 1;
+
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            output.push(format!("{CODE_INDENT}{ANSI_BOLD}{line}{ANSI_RESET}"));
+            continue;
+        }
+
+        // setext headings: a line of text underlined with `=` or `-`.
+        if let Some(next) = lines.get(i) {
+            if !line.trim().is_empty() && is_setext_underline(next) {
+                output.push(render_heading(line));
+                i += 1;
+                continue;
+            }
+        }
+
+        if let Some(heading) = strip_atx_heading(line) {
+            output.push(render_heading(heading));
+        } else if is_horizontal_rule(line) {
+            output.push("─".repeat(40));
+        } else if let Some(item) = strip_list_marker(line) {
+            output.push(format!("  • {}", render_inline(item)));
+        } else {
+            output.push(render_inline(line));
+        }
+    }
+
+    let mut rendered = output.join("\n");
+    rendered.push('\n');
+    rendered
+}
+
+fn render_heading(text: &str) -> String {
+    format!("{ANSI_BOLD}{}{ANSI_RESET}", strip_inline(text.trim()))
+}
+
+fn is_setext_underline(line: &str) -> bool {
+    let line = line.trim();
+    line.len() >= 3 && (line.bytes().all(|c| c == b'=') || line.bytes().all(|c| c == b'-'))
+}
+
+fn is_horizontal_rule(line: &str) -> bool {
+    let line: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    line.len() >= 3
+        && (line.bytes().all(|c| c == b'-')
+            || line.bytes().all(|c| c == b'*')
+            || line.bytes().all(|c| c == b'_'))
+}
+
+fn strip_atx_heading(line: &str) -> Option<&str> {
+    let level = line.bytes().take_while(|c| *c == b'#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    line[level..]
+        .strip_prefix(' ')
+        .map(|s| s.trim_end_matches('#').trim_end())
+}
+
+fn strip_list_marker(line: &str) -> Option<&str> {
+    line.strip_prefix("* ")
+        .or_else(|| line.strip_prefix("- "))
+        .or_else(|| line.strip_prefix("+ "))
+}
+
+/// Styles emphasis, strong emphasis and code spans, unescapes backslash escapes and replaces links with their text.
+fn render_inline(line: &str) -> String {
+    render_spans(line, true)
+}
+
+/// Like [`render_inline`], but drops all styling. Used for headings, which are rendered bold as a whole.
+fn strip_inline(line: &str) -> String {
+    render_spans(line, false)
+}
+
+fn render_spans(line: &str, styled: bool) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut out = String::with_capacity(line.len());
+
+    let mut italic = false;
+    let mut bold = false;
+    let mut i = 0;
+
+    let style = |out: &mut String, italic: bool, bold: bool| {
+        if !styled {
+            return;
+        }
+        out.push_str(ANSI_RESET);
+        if bold {
+            out.push_str(ANSI_BOLD);
+        }
+        if italic {
+            out.push_str(ANSI_ITALIC);
+        }
+    };
+
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if i + 1 < chars.len() && chars[i + 1].is_ascii_punctuation() => {
+                out.push(chars[i + 1]);
+                i += 2;
+            }
+            '`' => {
+                let Some(len) = chars[i + 1..].iter().position(|c| *c == '`') else {
+                    out.push('`');
+                    i += 1;
+                    continue;
+                };
+                let code: String = chars[i + 1..i + 1 + len].iter().collect();
+                if styled {
+                    out.push_str(ANSI_BOLD);
+                    out.push_str(&code);
+                    style(&mut out, italic, bold);
+                } else {
+                    out.push_str(&code);
+                }
+                i += len + 2;
+            }
+            '*' | '_' if chars.get(i + 1) == Some(&chars[i]) => {
+                bold = !bold;
+                style(&mut out, italic, bold);
+                i += 2;
+            }
+            '*' | '_' if is_emphasis_delimiter(&chars, i, italic) => {
+                italic = !italic;
+                style(&mut out, italic, bold);
+                i += 1;
+            }
+            '[' => match parse_link(&chars[i..]) {
+                Some((text, consumed)) => {
+                    out.push_str(&render_spans(&text, styled));
+                    if italic || bold {
+                        style(&mut out, italic, bold);
+                    }
+                    i += consumed;
+                }
+                None => {
+                    out.push('[');
+                    i += 1;
+                }
+            },
+            c => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    if styled && (italic || bold) {
+        out.push_str(ANSI_RESET);
+    }
+
+    out
+}
+
+/// An opening delimiter must be followed by a non-space, a closing one preceded by a non-space.
+/// This keeps e.g. `2 * 3` and snake_case words intact.
+fn is_emphasis_delimiter(chars: &[char], i: usize, closing: bool) -> bool {
+    if closing {
+        i > 0 && !chars[i - 1].is_whitespace()
+    } else {
+        let next_is_text = chars.get(i + 1).is_some_and(|c| !c.is_whitespace());
+        let prev_is_word = i > 0 && chars[i - 1].is_alphanumeric();
+        next_is_text && !(chars[i] == '_' && prev_is_word)
+    }
+}
+
+/// Parses `[text](url)` at the start of `chars`, returning the link text and the number of chars consumed.
+fn parse_link(chars: &[char]) -> Option<(String, usize)> {
+    let text_end = chars.iter().position(|c| *c == ']')?;
+    if chars.get(text_end + 1) != Some(&'(') {
+        return None;
+    }
+    let url_len = chars[text_end + 2..].iter().position(|c| *c == ')')?;
+    let text = chars[1..text_end].iter().collect();
+    Some((text, text_end + url_len + 3))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::render;
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    #[test]
+    fn renders_setext_headings() {
+        let s = render("\\--- Day 1: Historian Hysteria ---\n----------\n");
+        assert_eq!(
+            s,
+            format!("{ANSI_BOLD}--- Day 1: Historian Hysteria ---{ANSI_RESET}\n")
+        );
+    }
+
+    #[test]
+    fn renders_atx_headings() {
+        let s = render("## Part Two ##");
+        assert_eq!(s, format!("{ANSI_BOLD}Part Two{ANSI_RESET}\n"));
+    }
+
+    #[test]
+    fn renders_emphasis() {
+        let s = render("The *Chief Historian* is **always** present.");
+        assert_eq!(
+            s,
+            format!(
+                "The {ANSI_RESET}{ANSI_ITALIC}Chief Historian{ANSI_RESET} is {ANSI_RESET}{ANSI_BOLD}always{ANSI_RESET} present.\n"
+            )
+        );
+    }
+
+    #[test]
+    fn keeps_non_emphasis_delimiters() {
+        assert_eq!(render("2 * 3 * 4"), "2 * 3 * 4\n");
+        assert_eq!(render("snake_case_word"), "snake_case_word\n");
+    }
+
+    #[test]
+    fn renders_code_blocks_verbatim() {
+        let s = render("```\n3   4\n*x*\n```\nafter");
+        assert_eq!(
+            s,
+            format!("    {ANSI_BOLD}3   4{ANSI_RESET}\n    {ANSI_BOLD}*x*{ANSI_RESET}\nafter\n")
+        );
+    }
+
+    #[test]
+    fn renders_inline_code_and_links() {
+        let s = render("Answer: `42`, see [the about page](/2024/about).");
+        assert_eq!(
+            s,
+            format!("Answer: {ANSI_BOLD}42{ANSI_RESET}, see the about page.\n")
+        );
+    }
+
+    #[test]
+    fn renders_lists_and_unescapes() {
+        let s = render("* item \\*one\\*\n- item two");
+        assert_eq!(s, "  • item *one*\n  • item two\n");
+    }
+}
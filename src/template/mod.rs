@@ -1,25 +1,70 @@
-use std::{env, fs};
+use std::{env, fmt::Debug, fs};
 
 pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod config;
 pub mod runner;
+pub mod visualize;
 
 pub use day::*;
+pub use input_source::InputSource;
+pub use params::*;
+pub use profiles::Profile;
+pub use year::*;
 
 mod day;
+mod input_shape;
+mod input_source;
+mod leaderboard;
+mod markdown;
+mod params;
+mod paths;
+mod profiles;
 mod readme_benchmarks;
 mod run_multi;
+mod templates;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+///
+/// The file is looked up in the data directory of the year of the running solution binary,
+/// see [`Year::of_current_bin`].
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
+    let year = Year::qualified(Year::of_current_bin());
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(paths::data_file(year, folder, &format!("{day}.txt")));
+    let f = fs::read_to_string(filepath);
+    f.expect("could not open input file")
+}
+
+/// Helper function that reads the puzzle input of a day.
+/// If the solution was invoked with `--profile <name>`, the input of that profile is read instead.
+/// `--example`, `--example-part` and `--input` read another input, see [`InputSource`].
+#[must_use]
+pub fn read_input(day: Day) -> String {
+    if let Some(input) = InputSource::current().read(day) {
+        return input;
+    }
+
+    let args: Vec<String> = env::args().collect();
+    let profile = args
+        .iter()
+        .position(|x| x == "--profile")
+        .and_then(|i| args.get(i + 1))
+        .map(|p| p.parse::<Profile>().expect("invalid profile name"));
+
+    let year = Year::qualified(Year::of_current_bin());
+    let filepath =
+        env::current_dir()
+            .unwrap()
+            .join(profiles::get_input_path(year, day, profile.as_ref()));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
@@ -27,31 +72,93 @@ pub fn read_file(folder: &str, day: Day) -> String {
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+    let year = Year::qualified(Year::of_current_bin());
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let filepath = cwd.join(paths::data_file(year, folder, &format!("{day}-{part}.txt")));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
+/// Helper function that reads an example by name: `examples` reads `examples/01.txt`, a part suffix
+/// like in `examples-2` reads `examples/01-2.txt`, see [`read_file_part`].
+#[must_use]
+pub fn read_example(name: &str, day: Day) -> String {
+    match name.rsplit_once('-') {
+        Some((folder, part)) if part.parse::<u8>().is_ok() => {
+            read_file_part(folder, day, part.parse().unwrap())
+        }
+        _ => read_file(name, day),
+    }
+}
+
+/// Runs a part on an example and compares the result with the expected answer, see [`aoc_tests!`].
+/// `expected` is either the answer itself or an `Option`, e.g. `None` while the answer is unknown.
+/// Parts that take [`Params`] are passed [`Params::Example`].
+#[track_caller]
+pub fn check_example<M, T: PartialEq + Debug>(
+    func: impl Part<M, T>,
+    name: &str,
+    example: &str,
+    day: Day,
+    expected: impl Into<Option<T>>,
+) {
+    let result = func.solve(&read_example(example, day), Params::Example);
+    assert_eq!(
+        result,
+        expected.into(),
+        "{name} returned a wrong answer for example \"{example}\""
+    );
+}
+
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Puzzles with another number of parts list their functions instead, which are numbered in order:
+/// `solution!(25, [part_one])` or `solution!(1, [part_one, part_two, part_three])`.
+///
+/// A part can have several named implementations, e.g. a naive and an optimised one. All of them
+/// are run and benchmarked, and have to return the same result:
+/// `solution!(17, [part_one, { generic: part_two_generic, specific: part_two }])`.
+///
+/// Parts either take the input only or the input and [`Params`], which are [`Params::Input`] unless
+/// the solution is run on an example, see [`InputSource::params`].
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [1, "part_one" => part_one] [2, "part_two" => part_two]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [1, "part_one" => part_one]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [2, "part_two" => part_two]);
+    };
+    ($day:expr, [$($parts:tt)+]) => {
+        $crate::solution!(@number $day, [], 0, $($parts)+);
+    };
+
+    // assigns consecutive part numbers to a list of functions or groups of named implementations.
+    (@number $day:expr, [$($numbered:tt)*], $part:expr $(,)?) => {
+        $crate::solution!(@impl $day, $($numbered)*);
+    };
+    (@number $day:expr, [$($numbered:tt)*], $part:expr, { $($name:ident: $func:expr),+ $(,)? } $(, $($rest:tt)*)?) => {
+        $crate::solution!(
+            @number $day,
+            [$($numbered)* [$part + 1, $(stringify!($name) => $func),+]],
+            $part + 1
+            $(, $($rest)*)?
+        );
+    };
+    (@number $day:expr, [$($numbered:tt)*], $part:expr, $func:expr $(, $($rest:tt)*)?) => {
+        $crate::solution!(
+            @number $day,
+            [$($numbered)* [$part + 1, stringify!($func) => $func]],
+            $part + 1
+            $(, $($rest)*)?
+        );
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, $( [$part:expr, $($name:expr => $func:expr),+] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -61,8 +168,68 @@ macro_rules! solution {
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            start_timeout();
+            let input = $crate::template::read_input(DAY);
+            let params = $crate::template::InputSource::current().params();
+            $(
+                run_part(
+                    &[$((
+                        $name,
+                        (&|input: &str| $crate::template::Part::solve(&$func, input, params))
+                            as &dyn Fn(&str) -> Option<_>,
+                    )),+],
+                    &input,
+                    DAY,
+                    $part,
+                );
+            )*
+        }
+    };
+}
+
+/// Generates a test for each pair of example and expected answer of a part:
+///
+/// ```ignore
+/// advent_of_code::aoc_tests!(
+///     part_one: [("examples", 11)],
+///     part_two: [("examples", 31), ("examples-2", 48)],
+/// );
+/// ```
+///
+/// The tests are named after the part, e.g. `examples::part_two::example` and `examples::part_two::next::example`
+/// for the second pair. A failing test reports the name of its example.
+#[macro_export]
+macro_rules! aoc_tests {
+    ($($func:ident: [$($case:tt),* $(,)?]),* $(,)?) => {
+        #[cfg(test)]
+        mod examples {
+            #[allow(unused_imports)]
+            use super::*;
+
+            $(
+                mod $func {
+                    #[allow(unused_imports)]
+                    use super::*;
+
+                    $crate::aoc_tests!(@cases $func $(, $case)*);
+                }
+            )*
+        }
+    };
+
+    // test names have to be identifiers, so each further case is nested in its own module.
+    (@cases $func:ident $(,)?) => {};
+    (@cases $func:ident, ($example:expr, $expected:expr) $(, $rest:tt)*) => {
+        #[test]
+        fn example() {
+            $crate::template::check_example($func, stringify!($func), $example, DAY, $expected);
+        }
+
+        mod next {
+            #[allow(unused_imports)]
+            use super::*;
+
+            $crate::aoc_tests!(@cases $func $(, $rest)*);
         }
     };
 }
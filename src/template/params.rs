@@ -0,0 +1,90 @@
+/// Parameters of a puzzle that are not part of its input, e.g. the size of a grid or the number of
+/// steps, which often differ between the examples and the real input.
+///
+/// Parts that need them take a second argument, `pub fn part_one(input: &str, params: Params)`,
+/// and pick their values with [`Params::pick`]. The runner passes [`Params::Input`], example tests
+/// pass [`Params::Example`]. The values come from the puzzle description and are the same for every
+/// account, so `pick` is the only way to set them and they cannot be overridden. Values that differ
+/// between inputs, e.g. the size of a grid, are read from the input instead.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Params {
+    /// The solution is run on the puzzle input.
+    #[default]
+    Input,
+    /// The solution is run on an example.
+    Example,
+}
+
+impl Params {
+    /// Picks the value for the example or the puzzle input, e.g. the grid size of a day:
+    /// `let (width, height) = params.pick((11, 7), (101, 103));`
+    pub fn pick<T>(self, example: T, input: T) -> T {
+        match self {
+            Self::Example => example,
+            Self::Input => input,
+        }
+    }
+
+    pub fn is_example(self) -> bool {
+        self == Self::Example
+    }
+}
+
+/// Marks parts that take the input only, see [`Part`].
+pub struct InputOnly;
+
+/// Marks parts that take the input and [`Params`], see [`Part`].
+pub struct WithParams;
+
+/// A solution part, either `fn(&str) -> Option<T>` or `fn(&str, Params) -> Option<T>`.
+/// The marker `M` only tells the two signatures apart.
+pub trait Part<M, T> {
+    fn solve(&self, input: &str, params: Params) -> Option<T>;
+}
+
+impl<F, T> Part<InputOnly, T> for F
+where
+    F: Fn(&str) -> Option<T>,
+{
+    fn solve(&self, input: &str, _: Params) -> Option<T> {
+        self(input)
+    }
+}
+
+impl<F, T> Part<WithParams, T> for F
+where
+    F: Fn(&str, Params) -> Option<T>,
+{
+    fn solve(&self, input: &str, params: Params) -> Option<T> {
+        self(input, params)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Params, Part};
+
+    fn input_only(input: &str) -> Option<usize> {
+        Some(input.len())
+    }
+
+    fn with_params(input: &str, params: Params) -> Option<usize> {
+        Some(input.len() * params.pick(10, 1000))
+    }
+
+    #[test]
+    fn picks_values() {
+        assert_eq!(Params::Example.pick(6, 1000), 6);
+        assert_eq!(Params::Input.pick(6, 1000), 1000);
+        assert_eq!(Params::default(), Params::Input);
+    }
+
+    #[test]
+    fn solves_both_signatures() {
+        assert_eq!(input_only.solve("abc", Params::Example), Some(3));
+        assert_eq!(with_params.solve("abc", Params::Example), Some(30));
+        assert_eq!(with_params.solve("abc", Params::Input), Some(3000));
+    }
+}
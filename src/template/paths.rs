@@ -0,0 +1,79 @@
+//! Locations of solutions and data files.
+
+use crate::template::{config, Day, Year};
+
+/// Name of the solution binary, e.g. `05` or `2023-05`.
+#[must_use]
+pub fn bin_name(year: Option<Year>, day: Day) -> String {
+    match year {
+        Some(year) => format!("{year}-{day}"),
+        None => day.to_string(),
+    }
+}
+
+#[must_use]
+pub fn bin_path(year: Option<Year>, day: Day) -> String {
+    format!("src/bin/{}.rs", bin_name(year, day))
+}
+
+/// Root of the data directory of a year, e.g. `data` or `data/2023`.
+/// The root is configured with `data_dir` in `aoc.toml`.
+#[must_use]
+pub fn data_dir(year: Option<Year>) -> String {
+    let root = &config::get().data_dir;
+    match year {
+        Some(year) => format!("{root}/{year}"),
+        None => root.clone(),
+    }
+}
+
+/// Path of the file of a day in one of the data folders, e.g. `data/2023/inputs/05.txt`.
+#[must_use]
+pub fn data_file(year: Option<Year>, folder: &str, file_name: &str) -> String {
+    format!("{}/{folder}/{file_name}", data_dir(year))
+}
+
+#[must_use]
+pub fn input_path(year: Option<Year>, day: Day) -> String {
+    data_file(year, "inputs", &format!("{day}.txt"))
+}
+
+#[must_use]
+pub fn example_path(year: Option<Year>, day: Day) -> String {
+    data_file(year, "examples", &format!("{day}.txt"))
+}
+
+#[must_use]
+pub fn puzzle_path(year: Option<Year>, day: Day) -> String {
+    data_file(year, "puzzles", &format!("{day}.md"))
+}
+
+#[must_use]
+pub fn timings_path(year: Option<Year>) -> String {
+    format!("{}/timings.json", data_dir(year))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{bin_name, bin_path, input_path, puzzle_path, timings_path};
+    use crate::{day, template::Year};
+
+    #[test]
+    fn builds_default_year_paths() {
+        assert_eq!(bin_name(None, day!(5)), "05");
+        assert_eq!(bin_path(None, day!(5)), "src/bin/05.rs");
+        assert_eq!(input_path(None, day!(5)), "data/inputs/05.txt");
+        assert_eq!(puzzle_path(None, day!(5)), "data/puzzles/05.md");
+        assert_eq!(timings_path(None), "data/timings.json");
+    }
+
+    #[test]
+    fn builds_year_qualified_paths() {
+        let year = Year::new(2023);
+        assert_eq!(bin_name(year, day!(5)), "2023-05");
+        assert_eq!(bin_path(year, day!(5)), "src/bin/2023-05.rs");
+        assert_eq!(input_path(year, day!(5)), "data/2023/inputs/05.txt");
+        assert_eq!(puzzle_path(year, day!(5)), "data/2023/puzzles/05.md");
+        assert_eq!(timings_path(year), "data/2023/timings.json");
+    }
+}
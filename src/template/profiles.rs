@@ -0,0 +1,193 @@
+//! Named input profiles in `data/inputs/<profile>/`, for sharing solutions between accounts.
+
+use std::{collections::HashMap, error::Error, fmt::Display, fs, path::Path, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{paths, Day, Year};
+
+/// The name of an input profile.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Profile(String);
+
+impl Profile {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns all profiles of a year, sorted alphabetically.
+    pub fn all(year: Option<Year>) -> Vec<Profile> {
+        let Ok(entries) = fs::read_dir(paths::data_file(year, "inputs", "")) else {
+            return vec![];
+        };
+
+        let mut profiles: Vec<Profile> = entries
+            .filter_map(Result::ok)
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter_map(|name| name.parse().ok())
+            .collect();
+
+        profiles.sort_unstable();
+        profiles
+    }
+
+    /// Returns whether the profile has an input for `day`.
+    pub fn has_input(&self, year: Option<Year>, day: Day) -> bool {
+        Path::new(&get_input_path(year, day, Some(self))).exists()
+    }
+}
+
+impl Display for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for Profile {
+    type Err = ProfileFromStrError;
+
+    /// A profile name is used as a directory name, so it may not contain path separators.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let is_valid = !s.is_empty()
+            && !s.starts_with('.')
+            && s.chars()
+                .all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+
+        if is_valid {
+            Ok(Self(s.into()))
+        } else {
+            Err(ProfileFromStrError)
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Profile`].
+#[derive(Debug)]
+pub struct ProfileFromStrError;
+
+impl Error for ProfileFromStrError {}
+
+impl Display for ProfileFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a profile name made of letters, digits, `-` and `_`")
+    }
+}
+
+#[must_use]
+pub fn get_input_path(year: Option<Year>, day: Day, profile: Option<&Profile>) -> String {
+    match profile {
+        Some(profile) => paths::data_file(year, "inputs", &format!("{profile}/{day}.txt")),
+        None => paths::input_path(year, day),
+    }
+}
+
+/// Known answers of a profile, keyed by day and part.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    data: HashMap<Day, HashMap<u8, String>>,
+}
+
+impl Answers {
+    /// Reads `answers.json` of a profile. If not present, returns empty answers.
+    pub fn read_from_file(year: Option<Year>, profile: &Profile) -> Result<Self, String> {
+        match fs::read_to_string(paths::data_file(
+            year,
+            "inputs",
+            &format!("{profile}/answers.json"),
+        )) {
+            Ok(json) => Answers::try_from(json.as_str()),
+            Err(_) => Ok(Answers::default()),
+        }
+    }
+
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        self.data.get(&day)?.get(&part).map(String::as_str)
+    }
+}
+
+/// Parses a document of the shape `{ "01": { "part_1": "11", "part_2": "31" } }`.
+impl TryFrom<&str> for Answers {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON file."))?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let mut data = HashMap::new();
+
+        for (day, parts) in json {
+            let day = Day::from_str(day).map_err(|_| format!("expected `{day}` to be a day."))?;
+            let parts = parts
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("expected answers of a day to be an object.")?;
+
+            let mut answers = HashMap::new();
+            for (part, answer) in parts {
+                let part = part
+                    .strip_prefix("part_")
+                    .and_then(|p| p.parse::<u8>().ok())
+                    .ok_or(format!("expected `{part}` to be of the form `part_N`."))?;
+
+                // answers may be written as JSON numbers or strings.
+                let answer = match answer {
+                    JsonValue::String(s) => s.clone(),
+                    JsonValue::Number(n) => format!("{n}"),
+                    _ => return Err(format!("expected answer to part {part} to be a string.")),
+                };
+                answers.insert(part, answer);
+            }
+
+            data.insert(day, answers);
+        }
+
+        Ok(Answers { data })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{get_input_path, Answers, Profile};
+    use crate::{day, template::Year};
+
+    #[test]
+    fn builds_input_paths() {
+        let profile: Profile = "alice".parse().unwrap();
+        assert_eq!(get_input_path(None, day!(5), None), "data/inputs/05.txt");
+        assert_eq!(
+            get_input_path(None, day!(5), Some(&profile)),
+            "data/inputs/alice/05.txt"
+        );
+        assert_eq!(
+            get_input_path(Year::new(2023), day!(5), Some(&profile)),
+            "data/2023/inputs/alice/05.txt"
+        );
+    }
+
+    #[test]
+    fn validates_names() {
+        assert!("alice".parse::<Profile>().is_ok());
+        assert!("bob_2".parse::<Profile>().is_ok());
+        assert!("".parse::<Profile>().is_err());
+        assert!("../alice".parse::<Profile>().is_err());
+        assert!(".hidden".parse::<Profile>().is_err());
+    }
+
+    #[test]
+    fn parses_answers() {
+        let json = r#"{ "01": { "part_1": "11", "part_2": 31 }, "17": { "part_1": "5,7,3,0" } }"#;
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.get(day!(1), 1), Some("11"));
+        assert_eq!(answers.get(day!(1), 2), Some("31"));
+        assert_eq!(answers.get(day!(17), 1), Some("5,7,3,0"));
+        assert_eq!(answers.get(day!(17), 2), None);
+        assert_eq!(answers.get(day!(2), 1), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        Answers::try_from(r#"{ "01": { "one": "11" } }"#).unwrap();
+    }
+}
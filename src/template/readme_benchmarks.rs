@@ -3,9 +3,18 @@
 use std::{fs, io};
 
 use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::{config, paths, Year};
 
-static MARKER: &str = "<!--- benchmarking table --->";
+/// Marker of the table of a year. The default year uses the configured marker as is,
+/// other years add the year to it, e.g. `<!--- benchmarking table 2023 --->`.
+fn get_marker(year: Option<Year>) -> String {
+    let marker = &config::get().readme_marker;
+    match (year, marker.strip_suffix(" --->")) {
+        (Some(year), Some(stem)) => format!("{stem} {year} --->"),
+        (Some(year), None) => format!("{marker} {year}"),
+        (None, _) => marker.clone(),
+    }
+}
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -25,13 +34,8 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,75 +56,125 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(
+    prefix: &str,
+    year: Option<Year>,
+    timings: Timings,
+    total_millis: f64,
+) -> String {
+    let marker = get_marker(year);
+    let header = match year {
+        Some(year) => format!("{prefix} {year} Benchmarks"),
+        None => format!("{prefix} Benchmarks"),
+    };
+
+    // one column per part of the day with the most parts.
+    let part_count = timings
+        .data
+        .iter()
+        .map(|t| t.parts.len())
+        .max()
+        .unwrap_or(2);
+
+    let part_headers: String = (1..=part_count).map(|p| format!(" Part {p} |")).collect();
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker.clone(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("| Day |{part_headers}"),
+        format!("{}| :---:  |", "| :---: ".repeat(part_count)),
     ];
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        let path = format!("./{}", paths::bin_path(year, timing.day));
+        let parts: String = (1..=part_count)
+            .map(|p| match timing.implementation(p) {
+                Some(name) => format!(" `{}` ({name}) |", timing.part(p).unwrap_or("-")),
+                None => format!(" `{}` |", timing.part(p).unwrap_or("-")),
+            })
+            .collect();
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) |{parts}",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    year: Option<Year>,
+    timings: Timings,
+    total_millis: f64,
+) -> Result<(), Error> {
+    let marker = get_marker(year);
+
+    // other years get their own section, which is appended on first use.
+    if year.is_some() && !s.contains(&marker) {
+        if !s.ends_with('\n') {
+            s.push('\n');
+        }
+        s.push_str(&format!("\n{marker}{marker}\n"));
+    }
+
+    let positions = locate_table(s, &marker)?;
+    let table = construct_table("##", year, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+/// Updates the benchmark table of a year in every configured README file.
+pub fn update(year: Option<Year>, timings: Timings) -> Result<(), Error> {
+    // drop stale timings of days that are not part of the event.
+    let days = Year::puzzle_count_of(year);
+    let timings = Timings {
+        data: timings.data.into_iter().filter(|t| t.day <= days).collect(),
+    };
+
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
+
+    for path in &config::get().readme_files {
+        let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+        update_content(&mut readme, year, timings.clone(), total_millis)?;
+        fs::write(path, &readme)?;
+    }
+
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::update_content;
+    use crate::{day, template::timings::Timing, template::timings::Timings, template::Year};
+
+    const MARKER: &str = "<!--- benchmarking table --->";
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    parts: vec![Some("10ms".into()), Some("20ms".into())],
+                    implementations: vec![],
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    parts: vec![Some("30ms".into()), Some("40ms".into())],
+                    implementations: vec![],
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
+                    parts: vec![Some("40ms".into()), Some("50ms".into())],
+                    implementations: vec![],
                     total_nanos: 9e+10,
                 },
             ],
@@ -131,28 +185,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +214,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -180,4 +234,51 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn appends_year_section() {
+        let mut s = format!("foo\n{}{}\nbaz\n", MARKER, MARKER);
+        update_content(&mut s, Year::new(2023), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::new(2023), get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches("<!--- benchmarking table 2023 --->").count(), 2);
+        assert_eq!(s.matches("## 2023 Benchmarks").count(), 1);
+        assert!(s.contains("| [Day 1](./src/bin/2023-01.rs) | `10ms` | `20ms` |"));
+    }
+
+    #[test]
+    fn formats_variable_part_counts() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let timings = Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    parts: vec![Some("1ms".into()), Some("2ms".into()), Some("3ms".into())],
+                    implementations: vec![],
+                    total_nanos: 6e+6,
+                },
+                Timing {
+                    day: day!(25),
+                    parts: vec![Some("4ms".into())],
+                    implementations: vec![],
+                    total_nanos: 4e+6,
+                },
+            ],
+        };
+        update_content(&mut s, None, timings, 10.0).unwrap();
+        assert!(
+            s.contains("| Day | Part 1 | Part 2 | Part 3 |\n| :---: | :---: | :---: | :---:  |")
+        );
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `1ms` | `2ms` | `3ms` |"));
+        assert!(s.contains("| [Day 25](./src/bin/25.rs) | `4ms` | `-` | `-` |"));
+    }
+
+    #[test]
+    fn names_fastest_implementations() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings.data[0].implementations = vec![None, Some("fast".into())];
+        update_content(&mut s, None, timings, 190.0).unwrap();
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` (fast) |"));
+    }
 }
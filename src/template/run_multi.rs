@@ -1,19 +1,26 @@
-use std::{collections::HashSet, io};
+use std::{collections::HashSet, fmt::Display, io, process::ExitStatus};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Day, Profile, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(
+    year: Option<Year>,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    part: Option<u8>,
+    profile: Option<&Profile>,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    all_days(year)
         .filter(|day| days_to_run.contains(day))
         .for_each(|day| {
             if need_space {
@@ -24,13 +31,13 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
-
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+            match child_commands::run_solution(year, day, is_timed, is_release, part, profile) {
+                Ok(output) if output.is_empty() => println!("Not solved."),
+                Ok(output) => {
+                    let val = child_commands::parse_exec_time(&output, day);
+                    timings.push(val);
+                }
+                Err(e) => eprintln!("Day {day} failed: {e}"),
             }
         });
 
@@ -46,11 +53,22 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
-#[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
     IO(io::Error),
+    /// The solution failed to build or run, e.g. because it panicked.
+    BadExitStatus(ExitStatus),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not read the output of the solution."),
+            Error::IO(e) => write!(f, "could not run the solution: {e}"),
+            Error::BadExitStatus(status) => write!(f, "the solution failed with {status}."),
+        }
+    }
 }
 
 impl From<std::io::Error> for Error {
@@ -59,16 +77,13 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::Error;
+    use crate::template::runner::SKIPPED;
+    use crate::template::timings::parse_duration;
+    use crate::template::{config, paths, Day, Profile, Year};
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -76,26 +91,49 @@ pub mod child_commands {
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day. With `part`, only that part is run.
+    pub fn run_solution(
+        year: Option<Year>,
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        part: Option<u8>,
+        profile: Option<&Profile>,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
+        if !Path::new(&paths::bin_path(year, day)).exists() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let bin_name = paths::bin_name(year, day);
+        let mut args = vec!["run", "--quiet", "--bin", &bin_name];
 
         if is_release {
             args.push("--release");
         }
 
+        args.push("--");
+
+        // command-line overrides of `aoc.toml` apply to the solution as well.
+        let forwarded = config::get().forwarded_args();
+        args.extend(forwarded.iter().map(String::as_str));
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        let part = part.map(|part| part.to_string());
+        if let Some(part) = &part {
+            args.push("--part");
+            args.push(part);
+        }
+
+        if let Some(profile) = profile {
+            args.push("--profile");
+            args.push(profile.as_str());
+        }
+
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
@@ -123,7 +161,11 @@ pub mod child_commands {
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
+        let status = cmd.wait()?;
+
+        if !status.success() {
+            return Err(Error::BadExitStatus(status));
+        }
 
         Ok(output)
     }
@@ -131,41 +173,131 @@ pub mod child_commands {
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
-            part_1: None,
-            part_2: None,
+            parts: vec![],
+            implementations: vec![],
             total_nanos: 0_f64,
         };
+        // time of the fastest implementation of each part.
+        let mut fastest: Vec<Option<f64>> = vec![];
+
+        for line in output {
+            let Some((part, name)) = parse_part_label(line) else {
+                continue;
+            };
+
+            // parts without a benchmark (e.g. unsolved or skipped ones) are kept as `None`.
+            if timings.parts.len() < part {
+                timings.parts.resize(part, None);
+                timings.implementations.resize(part, None);
+                fastest.resize(part, None);
+            }
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+            if !line.contains(" samples)") {
+                continue;
+            }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+            let Some((timing_str, nanos)) = parse_time(line) else {
+                eprintln!("Could not parse timings from line: {line}");
+                continue;
+            };
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+            if fastest[part - 1].is_some_and(|fastest| fastest <= nanos) {
+                continue;
+            }
 
-                timings.total_nanos += nanos;
-            });
+            fastest[part - 1] = Some(nanos);
+            timings.parts[part - 1] = Some(timing_str.into());
+            timings.implementations[part - 1] = name.map(String::from);
+        }
+
+        timings.total_nanos = fastest.iter().flatten().sum();
+
+        // days without several implementations of a part don't store any names.
+        if timings.implementations.iter().all(Option::is_none) {
+            timings.implementations.clear();
+        }
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// Parse the part number and implementation of a result line,
+    /// e.g. `(3, None)` for `Part 3: 42 (1.2ms)` and `(2, Some("naive"))` for `Part 2 (naive): 42 (1.2ms)`.
+    fn parse_part_label(line: &str) -> Option<(usize, Option<&str>)> {
+        let label = line.split(':').next()?.strip_prefix("Part ")?;
+        let (part, name) = match label.split_once(" (") {
+            Some((part, name)) => (part, Some(name.strip_suffix(')')?)),
+            None => (label, None),
+        };
+        let part = part.parse().ok().filter(|part| *part > 0)?;
+        Some((part, name))
+    }
+
+    /// Parse the results of each part from the output of a solution.
+    /// Parts that did not produce a result are returned as `None`.
+    pub fn parse_results(output: &[String]) -> Vec<(u8, Option<String>)> {
+        let mut results = vec![];
+
+        for (i, line) in output.iter().enumerate() {
+            // intermediate output is overwritten by the final result with a carriage return.
+            let line = strip_ansi(line.rsplit('\r').next().unwrap_or_default());
+
+            let Some((part, _)) = parse_part_label(&line) else {
+                continue;
+            };
+            let Some((_, result)) = line.split_once(": ") else {
+                continue;
+            };
+            let Ok(part) = u8::try_from(part) else {
+                continue;
+            };
+            if result == SKIPPED {
+                continue;
+            }
+            // all implementations of a part return the same result, see `runner::run_part`.
+            if results.iter().any(|(p, _)| *p == part) {
+                continue;
+            }
+
+            let result = if result.starts_with('✖') {
+                None
+            } else if result.starts_with('▼') {
+                // multi-line results are printed on the lines following the part.
+                let lines: Vec<String> = output[i + 1..]
+                    .iter()
+                    .map(|l| strip_ansi(l))
+                    .take_while(|l| !l.is_empty() && !l.starts_with("Part "))
+                    .collect();
+                Some(lines.join("\n"))
+            } else {
+                let result = match result.rfind(" (") {
+                    Some(idx) if result.ends_with(')') => &result[..idx],
+                    _ => result,
+                };
+                Some(result.to_string())
+            };
+
+            results.push((part, result));
+        }
+
+        results
+    }
+
+    fn strip_ansi(s: &str) -> String {
+        let mut out = String::with_capacity(s.len());
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                // skip a CSI sequence up to and including its final byte.
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            } else {
+                out.push(c);
+            }
+        }
+        out
     }
 
     fn parse_time(line: &str) -> Option<(&str, f64)> {
@@ -179,14 +311,7 @@ pub mod child_commands {
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        Some((str_timing, parse_duration(str_timing)?))
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -205,7 +330,7 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_exec_time, parse_results};
 
         use crate::day;
 
@@ -220,8 +345,8 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.part(1).unwrap(), "74.13ns");
+            assert_eq!(res.part(2).unwrap(), "74.13ms");
         }
 
         #[test]
@@ -235,8 +360,8 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part(1).unwrap(), "2s");
+            assert_eq!(res.part(2).unwrap(), "100ms");
         }
 
         #[test]
@@ -250,8 +375,104 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.parts, vec![None, None]);
+        }
+
+        #[test]
+        fn parses_any_number_of_parts() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 1 (1ms @ 10 samples)".into(),
+                    "Part 2: ✖        ".into(),
+                    "Part 3: 3 (3ms @ 10 samples)".into(),
+                    "Part 10: 10 (10ms @ 10 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.parts.len(), 10);
+            assert_eq!(res.part(1).unwrap(), "1ms");
+            assert_eq!(res.part(2), None);
+            assert_eq!(res.part(3).unwrap(), "3ms");
+            assert_eq!(res.part(10).unwrap(), "10ms");
+            assert_approx_eq!(res.total_nanos, 14_000_000_f64);
+        }
+
+        #[test]
+        fn picks_fastest_implementations() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 1 (1ms @ 10 samples)".into(),
+                    "Part 2 (naive): 2 (20ms @ 10 samples)".into(),
+                    "Part 2 (fast): 2 (2ms @ 10 samples)".into(),
+                    "Part 2 (simd): 2 (5ms @ 10 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part(2).unwrap(), "2ms");
+            assert_eq!(res.implementation(1), None);
+            assert_eq!(res.implementation(2), Some("fast"));
+            assert_approx_eq!(res.total_nanos, 3_000_000_f64);
+        }
+
+        #[test]
+        fn omits_names_of_single_implementations() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 1 (1ms @ 10 samples)".into(),
+                    "Part 2: 2 (2ms @ 10 samples)".into(),
+                ],
+                day!(1),
+            );
+            assert!(res.implementations.is_empty());
+        }
+
+        #[test]
+        fn parses_results() {
+            let res = parse_results(&[
+                "Part 1: \x1b[1m11\x1b[0m > benching\rPart 1: \x1b[1m11\x1b[0m (74.13ns @ 100000 samples)".into(),
+                "Part 2: \x1b[1m5,7,3,0\x1b[0m (1.2ms)".into(),
+                "".into(),
+            ]);
+            assert_eq!(
+                res,
+                vec![(1, Some("11".into())), (2, Some("5,7,3,0".into()))]
+            );
+        }
+
+        #[test]
+        fn parses_missing_and_multiline_results() {
+            let res = parse_results(&[
+                "Part 1: ✖\rPart 1: ✖             ".into(),
+                "Part 2: ▼ \rPart 2: ▼  (1.2ms)".into(),
+                "#..".into(),
+                ".#.".into(),
+            ]);
+            assert_eq!(res, vec![(1, None), (2, Some("#..\n.#.".into()))]);
+        }
+
+        #[test]
+        fn parses_results_of_implementations() {
+            let res = parse_results(&[
+                "Part 1: 11 (1.2ms)".into(),
+                "Part 2 (naive): 31 (2.4ms)".into(),
+                "Part 2 (fast): 31 (1.2ms)".into(),
+            ]);
+            assert_eq!(res, vec![(1, Some("11".into())), (2, Some("31".into()))]);
+        }
+
+        #[test]
+        fn keeps_skipped_parts() {
+            let output = [
+                "Part 1: 11 (1ms @ 10 samples)".into(),
+                "Part 2: skipped".into(),
+            ];
+            assert_eq!(
+                parse_exec_time(&output, day!(1)).parts,
+                vec![Some("1ms".into()), None]
+            );
+            assert_eq!(parse_results(&output), vec![(1, Some("11".into()))]);
         }
     }
 }
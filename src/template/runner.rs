@@ -4,27 +4,100 @@ use std::hint::black_box;
 use std::io::{stdout, Write};
 use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, process, thread};
 
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, config, visualize, Day, Year, ANSI_ITALIC, ANSI_RESET};
+
+/// Aborts the solution once the timeout configured in `aoc.toml` or with `--timeout` has passed.
+pub fn start_timeout() {
+    if let Some(timeout) = config::get().solution_timeout {
+        thread::spawn(move || {
+            thread::sleep(timeout);
+            println!();
+            eprintln!("Solution timed out after {}s.", timeout.as_secs());
+            process::exit(1);
+        });
+    }
+}
+
+/// A named implementation of a part, see [`solution!`](crate::solution).
+pub type Implementation<'a, T> = (&'a str, &'a dyn Fn(&str) -> Option<T>);
+
+/// Printed instead of the result of parts skipped with `--part`.
+pub const SKIPPED: &str = "skipped";
+
+/// Runs and benchmarks each implementation of a part, unless another part was selected with `--part`. Parts with several implementations are
+/// labelled with the name of each, e.g. `Part 2 (naive)`, and exit with an error if their results differ.
+pub fn run_part<T: Display + PartialEq>(
+    implementations: &[Implementation<T>],
+    input: &str,
+    day: Day,
+    part: u8,
+) {
+    if selected_part().is_some_and(|selected| selected != part) {
+        // still listed, so that `time --part` knows how many parts the day has.
+        println!("Part {part}: {SKIPPED}");
+        return;
+    }
+
+    let mut results = Vec::with_capacity(implementations.len());
+    visualize::start(part);
+
+    for (name, func) in implementations {
+        let part_str = if implementations.len() == 1 {
+            format!("Part {part}")
+        } else {
+            format!("Part {part} ({name})")
+        };
+
+        let (result, duration, samples) = run_timed(func, input, |result| {
+            // only the first run is visualized, not the runs of `--time` or of other implementations.
+            visualize::finish();
+            print_result(result, &part_str, "");
+        });
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+        print_result(&result, &part_str, &format_duration(&duration, samples));
+        results.push((name, result));
+    }
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let Some(((first_name, first), rest)) = results.split_first() else {
+        return;
+    };
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    if let Some((name, result)) = rest.iter().find(|(_, result)| result != first) {
+        eprintln!(
+            "Implementations of part {part} disagree: \"{first_name}\" returned {}, \"{name}\" returned {}.",
+            format_result(first),
+            format_result(result)
+        );
+        process::exit(1);
+    }
 
-    if let Some(result) = result {
+    if let Some((_, Some(result))) = results.into_iter().next() {
         submit_result(result, day, part);
     }
 }
 
+/// The part selected with `--part`, if any. All other parts are skipped.
+fn selected_part() -> Option<u8> {
+    let args: Vec<String> = env::args().collect();
+    args.iter()
+        .position(|x| x == "--part")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|x| x.parse().ok())
+}
+
+fn format_result<T: Display>(result: &Option<T>) -> String {
+    match result {
+        Some(result) => result.to_string(),
+        None => "✖".into(),
+    }
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched (approx. the configured budget of execution time or 10 samples, whatever take longer.)
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
@@ -58,8 +131,9 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
+    let budget = config::get().bench_budget;
     let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+        (budget.as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -165,5 +239,10 @@ fn submit_result<T: Display>(
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(aoc_cli::submit(
+        Year::qualified(Year::of_current_bin()),
+        day,
+        part,
+        &result.to_string(),
+    ))
 }
@@ -0,0 +1,319 @@
+//! Module templates used by `scaffold`, see the `templates/` directory.
+
+use std::{fs, path::Path};
+
+use crate::template::{Day, Year};
+
+const TEMPLATES_DIR: &str = "templates";
+
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+const BUILTIN_TEMPLATE: &str =
+    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+
+/// Values substituted into a template.
+pub struct Variables {
+    pub day: Day,
+    pub year: Option<Year>,
+    /// Title of the puzzle, if it has been downloaded already.
+    pub title: Option<String>,
+    /// Width and height of grid inputs, see [`InputShape`](crate::template::input_shape::InputShape).
+    pub dimensions: Option<(usize, usize)>,
+}
+
+/// Reads a template by name. Values that look like a path (e.g. `src/template.txt`) are read as is.
+pub fn read(name: &str) -> Result<String, String> {
+    if name.contains('/') || name.ends_with(".txt") {
+        return fs::read_to_string(name).map_err(|e| format!("could not read \"{name}\": {e}"));
+    }
+
+    let path = format!("{TEMPLATES_DIR}/{name}.txt");
+
+    match fs::read_to_string(&path) {
+        Ok(template) => Ok(template),
+        Err(_) if name == DEFAULT_TEMPLATE => Ok(BUILTIN_TEMPLATE.into()),
+        Err(_) => Err(format!(
+            "template \"{name}\" not found. Available templates: {}.",
+            available().join(", ")
+        )),
+    }
+}
+
+/// Names of all templates, including the built-in `default`.
+pub fn available() -> Vec<String> {
+    let mut names = vec![DEFAULT_TEMPLATE.to_string()];
+
+    if let Ok(entries) = fs::read_dir(TEMPLATES_DIR) {
+        names.extend(entries.filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "txt" {
+                return None;
+            }
+            Some(path.file_stem()?.to_str()?.to_string())
+        }));
+    }
+
+    names.sort_unstable();
+    names.dedup();
+    names
+}
+
+/// Replaces the variables of a template:
+/// - `%DAY_NUMBER%`: the day without padding, e.g. `5`.
+/// - `%DAY%`: the padded day, e.g. `05`.
+/// - `%YEAR%`: the year of the puzzle, e.g. `2024`.
+/// - `%DATE%`: the date the puzzle unlocked, e.g. `2024-12-05`.
+/// - `%TITLE%`: the title of the puzzle, e.g. `Print Queue`. Falls back to `Day 5`.
+/// - `%WIDTH%`, `%HEIGHT%`: the dimensions of a grid input. `0` if the input is not a grid.
+pub fn render(template: &str, variables: &Variables) -> String {
+    let day = variables.day;
+    let year = variables.year.map(|y| y.to_string()).unwrap_or_default();
+    let date = variables
+        .year
+        .map(|y| format!("{y}-12-{day}"))
+        .unwrap_or_default();
+    let title = variables
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("Day {}", day.into_inner()));
+    let (width, height) = variables.dimensions.unwrap_or_default();
+
+    template
+        .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+        .replace("%DAY%", &day.to_string())
+        .replace("%YEAR%", &year)
+        .replace("%DATE%", &date)
+        .replace("%TITLE%", &title)
+        .replace("%WIDTH%", &width.to_string())
+        .replace("%HEIGHT%", &height.to_string())
+}
+
+/// Turns the solution of another day into the module of `day`: the `solution!` day is replaced and
+/// the expectations of the tests in `mod tests` and of [`aoc_tests!`](crate::aoc_tests) are reset to `None`,
+/// as the new example has different answers. Asserts in the solution itself are kept.
+pub fn from_existing(source: &str, day: Day) -> String {
+    let mut module = String::with_capacity(source.len());
+    let mut rest = source;
+
+    if let Some(start) = rest.find("solution!(") {
+        let start = start + "solution!(".len();
+        let end = rest[start..]
+            .find([',', ')'])
+            .map_or(start, |end| start + end);
+        module.push_str(&rest[..start]);
+        module.push_str(&day.into_inner().to_string());
+        rest = &rest[end..];
+    }
+
+    if let Some(start) = rest.find("aoc_tests!(") {
+        let open = start + "aoc_tests!".len();
+        if let Some(close) = find_closing_paren(&rest[open..]).map(|close| open + close) {
+            module.push_str(&rest[..open]);
+            module.push_str(&reset_examples(&rest[open..close]));
+            rest = &rest[close..];
+        }
+    }
+
+    // asserts before the tests module belong to the solution and are kept.
+    let tests = rest.find("mod tests").unwrap_or(rest.len());
+    module.push_str(&rest[..tests]);
+    rest = &rest[tests..];
+
+    while let Some(start) = rest.find("assert_eq!(") {
+        let open = start + "assert_eq!".len();
+        let Some(close) = find_closing_paren(&rest[open..]).map(|close| open + close) else {
+            break;
+        };
+
+        module.push_str(&rest[..start]);
+        if rest[open + 1..close].trim_start().starts_with("result") {
+            module.push_str("assert_eq!(result, None)");
+        } else {
+            module.push_str(&rest[start..=close]);
+        }
+        rest = &rest[close + 1..];
+    }
+
+    module.push_str(rest);
+    module
+}
+
+/// Replaces the answers of the `("example", answer)` pairs of [`aoc_tests!`](crate::aoc_tests) with `None`.
+fn reset_examples(cases: &str) -> String {
+    let mut reset = String::with_capacity(cases.len());
+    let mut rest = cases;
+
+    while let Some(start) = rest.find("(\"") {
+        let name_end = rest[start + 2..].find('"').map(|end| start + 2 + end);
+        let close = find_closing_paren(&rest[start..]).map(|close| start + close);
+        let (Some(name_end), Some(close)) = (name_end, close) else {
+            break;
+        };
+
+        reset.push_str(&rest[..=name_end]);
+        reset.push_str(", None)");
+        rest = &rest[close + 1..];
+    }
+
+    reset.push_str(rest);
+    reset
+}
+
+/// Returns the index of the parenthesis that closes the one `s` starts with.
+fn find_closing_paren(s: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Reads the title from a cached puzzle description, see [`puzzle_title`].
+pub fn read_puzzle_title(path: &str) -> Option<String> {
+    if !Path::new(path).exists() {
+        return None;
+    }
+    puzzle_title(&fs::read_to_string(path).ok()?)
+}
+
+/// Extracts the title from a puzzle description, e.g. `Print Queue` from `--- Day 5: Print Queue ---`.
+pub fn puzzle_title(puzzle: &str) -> Option<String> {
+    puzzle.lines().find_map(|line| {
+        let line = line.trim().trim_start_matches('#').trim_start();
+        // `aoc-cli` escapes the leading dashes.
+        let line = line.trim_start_matches('\\').strip_prefix("--- Day ")?;
+        let (_, title) = line.split_once(": ")?;
+        Some(title.trim_end_matches('-').trim().to_string())
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{from_existing, puzzle_title, render, Variables};
+    use crate::{day, template::Year};
+
+    #[test]
+    fn renders_variables() {
+        let variables = Variables {
+            day: day!(5),
+            year: Year::new(2024),
+            title: Some("Print Queue".into()),
+            dimensions: Some((130, 120)),
+        };
+        assert_eq!(
+            render(
+                "solution!(%DAY_NUMBER%); // %YEAR% day %DAY%: %TITLE% (%DATE%) %WIDTH%x%HEIGHT%",
+                &variables
+            ),
+            "solution!(5); // 2024 day 05: Print Queue (2024-12-05) 130x120"
+        );
+    }
+
+    #[test]
+    fn renders_fallbacks() {
+        let variables = Variables {
+            day: day!(5),
+            year: None,
+            title: None,
+            dimensions: None,
+        };
+        assert_eq!(
+            render("%TITLE%|%YEAR%|%DATE%|%WIDTH%", &variables),
+            "Day 5|||0"
+        );
+    }
+
+    #[test]
+    fn extracts_puzzle_titles() {
+        assert_eq!(
+            puzzle_title("\\--- Day 5: Print Queue ---\n----------\n\nText").as_deref(),
+            Some("Print Queue")
+        );
+        assert_eq!(
+            puzzle_title("## --- Day 12: Garden Groups: Part 2 ---").as_deref(),
+            Some("Garden Groups: Part 2")
+        );
+        assert_eq!(puzzle_title("no title here"), None);
+    }
+
+    #[test]
+    fn rewrites_existing_days() {
+        let source = r#"advent_of_code::solution!(18);
+
+pub fn part_one(input: &str) -> Option<u32> {
+    assert_eq!(input.len() % 2, 0);
+    let result = 22;
+    assert_eq!(result, 22);
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(22));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(
+            result,
+            Some(String::from("6,1"))
+        );
+    }
+}
+"#;
+        let module = from_existing(source, day!(22));
+
+        assert!(module.starts_with("advent_of_code::solution!(22);"));
+        assert!(module.contains("assert_eq!(input.len() % 2, 0);"));
+        assert!(module.contains("assert_eq!(result, 22);"));
+        assert_eq!(module.matches("assert_eq!(result, None);").count(), 2);
+        assert!(!module.contains("Some(22));"));
+        assert!(!module.contains("6,1"));
+    }
+
+    #[test]
+    fn resets_example_answers() {
+        let source = r#"advent_of_code::solution!(7);
+
+advent_of_code::aoc_tests!(
+    part_one: [("examples", 3749)],
+    part_two: [("examples", Some(String::from("a,b"))), ("examples-2", 11387)],
+);
+"#;
+        assert_eq!(
+            from_existing(source, day!(8)),
+            r#"advent_of_code::solution!(8);
+
+advent_of_code::aoc_tests!(
+    part_one: [("examples", None)],
+    part_two: [("examples", None), ("examples-2", None)],
+);
+"#
+        );
+    }
+
+    #[test]
+    fn rewrites_single_part_days() {
+        assert_eq!(
+            from_existing("advent_of_code::solution!(18, 1);", day!(22)),
+            "advent_of_code::solution!(22, 1);"
+        );
+    }
+}
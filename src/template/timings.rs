@@ -1,19 +1,35 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
-
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+use crate::template::{paths, Day, Year};
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    /// Benchmark times of each part, starting with part 1. Parts without a time are `None`.
+    pub parts: Vec<Option<String>>,
+    /// Name of the fastest implementation of each part. `None` for parts with a single implementation.
+    pub implementations: Vec<Option<String>>,
     pub total_nanos: f64,
 }
 
+impl Timing {
+    /// Returns the benchmark time of a part, starting at part 1.
+    pub fn part(&self, part: usize) -> Option<&str> {
+        self.parts.get(part.checked_sub(1)?)?.as_deref()
+    }
+
+    /// Returns the name of the fastest implementation of a part, starting at part 1.
+    pub fn implementation(&self, part: usize) -> Option<&str> {
+        self.implementations.get(part.checked_sub(1)?)?.as_deref()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        !self.parts.is_empty() && self.parts.iter().all(Option::is_some)
+    }
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -22,16 +38,16 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate timings to the JSON file of a year.
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(paths::timings_path(year))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+    /// Rehydrate timings from the JSON file of a year. If not present, returns empty timings.
+    pub fn read_from_file(year: Option<Year>) -> Self {
+        fs::read_to_string(paths::timings_path(year))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
@@ -55,15 +71,49 @@ impl Timings {
         Timings { data }
     }
 
+    /// Merge the times of a single part into `self`, keeping the stored times of the other parts.
+    pub fn merge_part(&self, new: &Self, part: usize) -> Self {
+        let Some(index) = part.checked_sub(1) else {
+            return self.clone();
+        };
+
+        let data: Vec<Timing> = new
+            .data
+            .iter()
+            .map(|timing| {
+                let Some(stored) = self.data.iter().find(|t| t.day == timing.day) else {
+                    return timing.clone();
+                };
+
+                let mut merged = stored.clone();
+                if merged.parts.len() <= index {
+                    merged.parts.resize(index + 1, None);
+                }
+                merged.parts[index] = timing.part(part).map(String::from);
+
+                if !merged.implementations.is_empty() || timing.implementation(part).is_some() {
+                    merged.implementations.resize(merged.parts.len(), None);
+                    merged.implementations[index] = timing.implementation(part).map(String::from);
+                }
+
+                // only the selected part ran, so the new total is the time of that part.
+                let stored_nanos = stored.part(part).and_then(parse_duration).unwrap_or(0_f64);
+                merged.total_nanos =
+                    (stored.total_nanos - stored_nanos).max(0_f64) + timing.total_nanos;
+                merged
+            })
+            .collect();
+
+        self.merge(&Timings { data })
+    }
+
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
     pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
-            .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+        self.data.iter().any(|t| t.day == day && t.is_complete())
     }
 }
 
@@ -114,24 +164,36 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
-
         map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "parts".into(),
+            JsonValue::Array(
+                value
+                    .parts
+                    .iter()
+                    .map(|part| match part {
+                        Some(x) => JsonValue::String(x.clone()),
+                        None => JsonValue::Null,
+                    })
+                    .collect(),
+            ),
         );
 
-        map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+        // only days with several implementations of a part store their names.
+        if value.implementations.iter().any(Option::is_some) {
+            map.insert(
+                "implementations".into(),
+                JsonValue::Array(
+                    value
+                        .implementations
+                        .iter()
+                        .map(|name| match name {
+                            Some(x) => JsonValue::String(x.clone()),
+                            None => JsonValue::Null,
+                        })
+                        .collect(),
+                ),
+            );
+        }
 
         JsonValue::Object(map)
     }
@@ -151,15 +213,35 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+        let parts = match json.get("parts") {
+            Some(parts) => parts
+                .get::<Vec<JsonValue>>()
+                .ok_or("Expected timing.parts to be an array.")?
+                .iter()
+                .map(parse_part)
+                .collect::<Option<Vec<_>>>()
+                .ok_or("Expected timing.parts to contain null or strings.")?,
+            // timings stored before parts were generalised have exactly two parts.
+            None => vec![
+                json.get("part_1")
+                    .and_then(parse_part)
+                    .ok_or("Expected timing.part_1 to be null or string.")?,
+                json.get("part_2")
+                    .and_then(parse_part)
+                    .ok_or("Expected timing.part_2 to be null or string.")?,
+            ],
+        };
 
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+        let implementations = match json.get("implementations") {
+            Some(names) => names
+                .get::<Vec<JsonValue>>()
+                .ok_or("Expected timing.implementations to be an array.")?
+                .iter()
+                .map(parse_part)
+                .collect::<Option<Vec<_>>>()
+                .ok_or("Expected timing.implementations to contain null or strings.")?,
+            None => vec![],
+        };
 
         let total_nanos = json
             .get("total_nanos")
@@ -168,13 +250,35 @@ impl TryFrom<&JsonValue> for Timing {
 
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            parts,
+            implementations,
             total_nanos,
         })
     }
 }
 
+/// Parses a formatted duration to nanoseconds, e.g. `1.5ms` to `1500000`.
+/// For the possible formats, see the `Debug` implementation of [`std::time::Duration`].
+pub fn parse_duration(s: &str) -> Option<f64> {
+    let parse_to_float = |postfix: &str| s.split(postfix).next()?.parse::<f64>().ok();
+
+    match s {
+        s if s.contains("ns") => parse_to_float("ns"),
+        s if s.contains("µs") => parse_to_float("µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float("ms").map(|x| x * 1_000_000_f64),
+        _ => parse_to_float("s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+/// Parses the time or implementation of a single part, `None` if the value is neither null nor a string.
+fn parse_part(value: &JsonValue) -> Option<Option<String>> {
+    if value.is_null() {
+        Some(None)
+    } else {
+        value.get::<String>().cloned().map(Some)
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
@@ -188,20 +292,20 @@ mod tests {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    parts: vec![Some("10ms".into()), Some("20ms".into())],
+                    implementations: vec![],
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    parts: vec![Some("30ms".into()), Some("40ms".into())],
+                    implementations: vec![],
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
+                    parts: vec![Some("40ms".into()), None],
+                    implementations: vec![],
                     total_nanos: 4e+10,
                 },
             ],
@@ -213,14 +317,33 @@ mod tests {
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "data": [{ "day": "01", "parts": ["1ms", null, "3ms"], "total_nanos": 1000000000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
-            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.part(1), Some("1ms"));
+            assert_eq!(timing.part(2), None);
+            assert_eq!(timing.part(3), Some("3ms"));
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.implementation(1), None);
+        }
+
+        #[test]
+        fn handles_implementations() {
+            let json = r#"{ "data": [{ "day": "01", "parts": ["1ms", "2ms"], "implementations": [null, "fast"], "total_nanos": 3000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.implementation(1), None);
+            assert_eq!(timing.implementation(2), Some("fast"));
+        }
+
+        #[test]
+        fn handles_legacy_json_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parts, vec![Some("1ms".to_string()), None]);
         }
 
         #[test]
@@ -247,6 +370,7 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::timings::Timings;
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
@@ -266,6 +390,24 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn roundtrips_parts() {
+            let timings = get_mock_timings();
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[2].parts, vec![Some("40ms".to_string()), None]);
+        }
+
+        #[test]
+        fn roundtrips_implementations() {
+            let mut timings = get_mock_timings();
+            timings.data[0].implementations = vec![None, Some("fast".into())];
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].implementation(2), Some("fast"));
+            assert!(parsed.data[1].implementations.is_empty());
+        }
     }
 
     mod is_day_complete {
@@ -279,13 +421,13 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    parts: vec![Some("1ms".into()), Some("2ms".into())],
+                    implementations: vec![],
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +435,13 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: None,
+                    parts: vec![Some("1ms".into()), None],
+                    implementations: vec![],
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -307,13 +449,27 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: None,
-                    part_2: None,
+                    parts: vec![None, None],
+                    implementations: vec![],
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+
+        #[test]
+        fn handles_single_part_days() {
+            let timings = Timings {
+                data: vec![Timing {
+                    day: day!(25),
+                    parts: vec![Some("1ms".into())],
+                    implementations: vec![],
+                    total_nanos: 1_000_000_000_f64,
+                }],
+            };
+
+            assert_eq!(timings.is_day_complete(day!(25)), true);
         }
     }
 
@@ -331,8 +487,8 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
-                    part_1: None,
-                    part_2: None,
+                    parts: vec![None, None],
+                    implementations: vec![],
                     total_nanos: 0_f64,
                 }],
             };
@@ -351,8 +507,8 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
-                    part_1: None,
-                    part_2: None,
+                    parts: vec![None, None],
+                    implementations: vec![],
                     total_nanos: 0_f64,
                 }],
             };
@@ -381,4 +537,75 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
         }
     }
+
+    mod merge_part {
+        use crate::{
+            day,
+            template::{
+                run_multi::child_commands::parse_exec_time,
+                timings::{parse_duration, Timing, Timings},
+                Day,
+            },
+        };
+
+        fn stored() -> Timings {
+            Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    parts: vec![Some("1ms".into()), Some("2ms".into())],
+                    implementations: vec![],
+                    total_nanos: 3_000_000_f64,
+                }],
+            }
+        }
+
+        fn measured(day: Day, parts: Vec<Option<String>>, total_nanos: f64) -> Timings {
+            Timings {
+                data: vec![Timing {
+                    day,
+                    parts,
+                    implementations: vec![],
+                    total_nanos,
+                }],
+            }
+        }
+
+        #[test]
+        fn keeps_other_part() {
+            let new = measured(day!(1), vec![None, Some("500µs".into())], 500_000_f64);
+            let merged = stored().merge_part(&new, 2);
+            assert_eq!(merged.data.len(), 1);
+            assert_eq!(
+                merged.data[0].parts,
+                vec![Some("1ms".into()), Some("500µs".into())]
+            );
+            assert_eq!(merged.data[0].total_nanos, 1_500_000_f64);
+        }
+
+        #[test]
+        fn adds_unstored_days() {
+            // output of `time 2 --part 1`.
+            let output = [
+                "Part 1: 42 (4ms @ 10 samples)".into(),
+                "Part 2: skipped".into(),
+            ];
+            let new = Timings {
+                data: vec![parse_exec_time(&output, day!(2))],
+            };
+            let merged = stored().merge_part(&new, 1);
+            assert_eq!(merged.data.len(), 2);
+            assert_eq!(merged.data[1].day, day!(2));
+            assert_eq!(merged.data[1].parts, vec![Some("4ms".into()), None]);
+            assert!(!merged.is_day_complete(day!(2)));
+        }
+
+        #[test]
+        fn parses_durations() {
+            assert_eq!(parse_duration("250ns"), Some(250_f64));
+            assert_eq!(parse_duration("1.5µs"), Some(1_500_f64));
+            assert_eq!(parse_duration("2ms"), Some(2_000_000_f64));
+            assert_eq!(parse_duration("1s"), Some(1_000_000_000_f64));
+            assert_eq!(parse_duration("fast"), None);
+        }
+    }
 }
@@ -0,0 +1,653 @@
+//! Frame-based visualisations of solutions, e.g. `cargo solve 15 --visualize terminal`.
+
+use std::{fmt::Display, path::PathBuf, str::FromStr};
+
+#[cfg(feature = "visualize")]
+use std::{
+    env, fs, io,
+    io::{stdout, Write},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+#[cfg(feature = "visualize")]
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+/// A grid of cells, each drawn as a character in the terminal and as a colored square in images.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    cells: Vec<u8>,
+}
+
+impl Frame {
+    pub fn new(width: usize, height: usize, fill: u8) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Creates a frame with the cell returned for each `(x, y)`.
+    pub fn from_fn(width: usize, height: usize, cell: impl Fn(usize, usize) -> u8) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| cell(x, y))
+            .collect();
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Creates a frame from the lines of a text grid, e.g. a puzzle input. Short lines are padded with spaces.
+    pub fn from_text(text: &str) -> Self {
+        let lines: Vec<&[u8]> = text.lines().map(str::as_bytes).collect();
+        let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+
+        Self::from_fn(width, lines.len(), |x, y| {
+            lines[y].get(x).copied().unwrap_or(b' ')
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the cell at `(x, y)`, or `None` if it is out of bounds.
+    pub fn get(&self, x: usize, y: usize) -> Option<u8> {
+        (x < self.width && y < self.height).then(|| self.cells[y * self.width + x])
+    }
+
+    /// Sets the cell at `(x, y)`. Cells out of bounds are ignored.
+    pub fn set(&mut self, x: usize, y: usize, cell: u8) {
+        if x < self.width && y < self.height {
+            self.cells[y * self.width + x] = cell;
+        }
+    }
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.cells.chunks(self.width.max(1)) {
+            writeln!(f, "{}", String::from_utf8_lossy(row))?;
+        }
+        Ok(())
+    }
+}
+
+/// Receives the frames of a solution, and plays or stores them once the solution has finished.
+pub trait Visualizer {
+    fn frame(&mut self, frame: Frame);
+
+    fn finish(&mut self) -> std::io::Result<()>;
+}
+
+/// Where `cargo solve --visualize <target>` sends the frames to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VisualizeTarget {
+    /// An ANSI animation in the terminal, `--visualize terminal`.
+    Terminal,
+    /// An animated GIF, e.g. `--visualize day15.gif`.
+    Gif(PathBuf),
+    /// An animated PNG, e.g. `--visualize day15.png`.
+    Apng(PathBuf),
+}
+
+impl FromStr for VisualizeTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let extension = s.rsplit_once('.').map(|(_, ext)| ext.to_lowercase());
+
+        match (s, extension.as_deref()) {
+            ("terminal", _) => Ok(Self::Terminal),
+            (_, Some("gif")) => Ok(Self::Gif(s.into())),
+            (_, Some("png" | "apng")) => Ok(Self::Apng(s.into())),
+            _ => Err(format!(
+                "unknown visualization target `{s}`, expected `terminal` or a `.gif` or `.png` file."
+            )),
+        }
+    }
+}
+
+impl Display for VisualizeTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Terminal => f.write_str("terminal"),
+            Self::Gif(path) | Self::Apng(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Emits a frame to the visualizer the solution was started with. The frame is only created if
+/// the `visualize` feature is enabled and a target was passed with `--visualize`.
+#[inline]
+pub fn emit(frame: impl FnOnce() -> Frame) {
+    #[cfg(feature = "visualize")]
+    if let Some(visualizer) = VISUALIZER.lock().unwrap().as_mut() {
+        visualizer.frame(frame());
+    }
+
+    #[cfg(not(feature = "visualize"))]
+    let _ = frame;
+}
+
+/// Emits only every `n`th frame of a part, e.g. to keep simulations with thousands of steps watchable.
+#[inline]
+pub fn emit_every(n: usize, frame: impl FnOnce() -> Frame) {
+    #[cfg(feature = "visualize")]
+    if EMITTED
+        .fetch_add(1, Ordering::Relaxed)
+        .is_multiple_of(n.max(1))
+    {
+        emit(frame);
+    }
+
+    #[cfg(not(feature = "visualize"))]
+    let _ = (n, frame);
+}
+
+/// Sets up a new capture of `part` for the target passed with `--visualize`, see [`run_part`](crate::template::runner::run_part).
+pub fn start(part: u8) {
+    #[cfg(feature = "visualize")]
+    {
+        let args: Vec<String> = env::args().collect();
+        let target = args
+            .iter()
+            .position(|x| x == "--visualize")
+            .and_then(|i| args.get(i + 1))
+            .map(|x| x.parse::<VisualizeTarget>().unwrap());
+
+        EMITTED.store(0, Ordering::Relaxed);
+        *VISUALIZER.lock().unwrap() = target.map(|target| target.for_part(part).visualizer());
+    }
+
+    #[cfg(not(feature = "visualize"))]
+    let _ = part;
+}
+
+/// Plays or stores the emitted frames of the current part. Frames emitted afterwards are dropped.
+pub fn finish() {
+    #[cfg(feature = "visualize")]
+    if let Some(mut visualizer) = VISUALIZER.lock().unwrap().take() {
+        if let Err(e) = visualizer.finish() {
+            eprintln!("Failed to write visualization: {e}");
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "visualize")]
+static VISUALIZER: Mutex<Option<Box<dyn Visualizer + Send>>> = Mutex::new(None);
+
+/// The number of frames passed to [`emit_every`] in the current part.
+#[cfg(feature = "visualize")]
+static EMITTED: AtomicUsize = AtomicUsize::new(0);
+
+/// The time each frame is shown for.
+#[cfg(feature = "visualize")]
+const FRAME_DELAY: Duration = Duration::from_millis(50);
+
+impl VisualizeTarget {
+    /// The target of a single part: files get the part as suffix, e.g. `day15.gif` becomes `day15-part1.gif`.
+    pub fn for_part(self, part: u8) -> Self {
+        let with_part = |path: PathBuf| {
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            let name = match path.extension() {
+                Some(ext) => format!("{stem}-part{part}.{}", ext.to_string_lossy()),
+                None => format!("{stem}-part{part}"),
+            };
+            path.with_file_name(name)
+        };
+
+        match self {
+            Self::Terminal => Self::Terminal,
+            Self::Gif(path) => Self::Gif(with_part(path)),
+            Self::Apng(path) => Self::Apng(with_part(path)),
+        }
+    }
+
+    #[cfg(feature = "visualize")]
+    fn visualizer(self) -> Box<dyn Visualizer + Send> {
+        match self {
+            Self::Terminal => Box::<Terminal>::default(),
+            Self::Gif(path) => Box::new(Gif(Recording::new(path))),
+            Self::Apng(path) => Box::new(Apng(Recording::new(path))),
+        }
+    }
+}
+
+/// Plays the frames as an animation, highlighting all cells except walls and empty space.
+#[cfg(feature = "visualize")]
+#[derive(Default)]
+struct Terminal {
+    frames: Vec<Frame>,
+}
+
+#[cfg(feature = "visualize")]
+impl Visualizer for Terminal {
+    fn frame(&mut self, frame: Frame) {
+        self.frames.push(frame);
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        let mut stdout = stdout();
+
+        for (i, frame) in self.frames.iter().enumerate() {
+            let mut screen = format!("\x1b[2J\x1b[HFrame {}/{}\n", i + 1, self.frames.len());
+            for row in frame.cells.chunks(frame.width.max(1)) {
+                for &cell in row {
+                    match cell {
+                        b'.' | b'#' | b' ' => screen.push(char::from(cell)),
+                        _ => {
+                            screen.push_str(&format!("{ANSI_BOLD}{}{ANSI_RESET}", char::from(cell)))
+                        }
+                    }
+                }
+                screen.push('\n');
+            }
+
+            stdout.write_all(screen.as_bytes())?;
+            stdout.flush()?;
+            thread::sleep(FRAME_DELAY);
+        }
+
+        Ok(())
+    }
+}
+
+/// The frames of an image file.
+#[cfg(feature = "visualize")]
+struct Recording {
+    path: PathBuf,
+    frames: Vec<Frame>,
+}
+
+#[cfg(feature = "visualize")]
+impl Recording {
+    fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            frames: vec![],
+        }
+    }
+}
+
+/// Maps the cells of all frames to a shared palette and scales each cell to a square of pixels.
+#[cfg(feature = "visualize")]
+struct Canvas {
+    width: usize,
+    height: usize,
+    scale: usize,
+    palette: Vec<[u8; 3]>,
+    indices: [u8; 256],
+}
+
+#[cfg(feature = "visualize")]
+impl Canvas {
+    /// Images are scaled to about 512 pixels.
+    const SIZE: usize = 512;
+
+    fn new(frames: &[Frame]) -> Self {
+        let width = frames.iter().map(Frame::width).max().unwrap_or(0);
+        let height = frames.iter().map(Frame::height).max().unwrap_or(0);
+
+        let mut used = [false; 256];
+        used[usize::from(b' ')] = true;
+        for &cell in frames.iter().flat_map(|frame| &frame.cells) {
+            used[usize::from(cell)] = true;
+        }
+
+        let mut palette = vec![];
+        let mut indices = [0; 256];
+        for cell in (0..=u8::MAX).filter(|&cell| used[usize::from(cell)]) {
+            indices[usize::from(cell)] = u8::try_from(palette.len()).unwrap();
+            palette.push(color(cell));
+        }
+
+        Self {
+            width,
+            height,
+            scale: (Self::SIZE / width.max(height).max(1)).clamp(1, 8),
+            palette,
+            indices,
+        }
+    }
+
+    fn pixel_width(&self) -> usize {
+        self.width * self.scale
+    }
+
+    fn pixel_height(&self) -> usize {
+        self.height * self.scale
+    }
+
+    /// The palette index of each pixel, row by row.
+    fn pixels(&self, frame: &Frame) -> Vec<u8> {
+        let mut pixels = Vec::with_capacity(self.pixel_width() * self.pixel_height());
+
+        for y in 0..self.pixel_height() {
+            for x in 0..self.pixel_width() {
+                let cell = frame.get(x / self.scale, y / self.scale).unwrap_or(b' ');
+                pixels.push(self.indices[usize::from(cell)]);
+            }
+        }
+
+        pixels
+    }
+}
+
+/// Walls are grey, digits a gradient and all other cells except empty space have a bright color.
+#[cfg(feature = "visualize")]
+fn color(cell: u8) -> [u8; 3] {
+    const BRIGHT: [[u8; 3]; 6] = [
+        [230, 80, 70],
+        [250, 200, 60],
+        [90, 200, 110],
+        [80, 170, 240],
+        [200, 110, 230],
+        [240, 140, 60],
+    ];
+
+    match cell {
+        b'.' | b' ' => [20, 20, 36],
+        b'#' => [130, 130, 140],
+        b'0'..=b'9' => {
+            let level = (cell - b'0') * 25;
+            [30 + level / 2, 60 + level, 40 + level / 3]
+        }
+        _ => BRIGHT[usize::from(cell) % BRIGHT.len()],
+    }
+}
+
+#[cfg(feature = "visualize")]
+fn pixel_size(size: usize) -> io::Result<u16> {
+    u16::try_from(size).map_err(|_| io::Error::other("visualization is too large"))
+}
+
+/// An animated GIF with a global palette.
+#[cfg(feature = "visualize")]
+struct Gif(Recording);
+
+#[cfg(feature = "visualize")]
+impl Gif {
+    fn encode(frames: &[Frame]) -> io::Result<Vec<u8>> {
+        let canvas = Canvas::new(frames);
+        let width = pixel_size(canvas.pixel_width())?.to_le_bytes();
+        let height = pixel_size(canvas.pixel_height())?.to_le_bytes();
+        let delay = u16::try_from(FRAME_DELAY.as_millis() / 10)
+            .unwrap()
+            .to_le_bytes();
+
+        let mut out = b"GIF89a".to_vec();
+        // global palette of 256 colors.
+        out.extend(width);
+        out.extend(height);
+        out.extend([0xF7, 0, 0]);
+        for i in 0..256 {
+            out.extend(canvas.palette.get(i).unwrap_or(&[0, 0, 0]));
+        }
+        // loop forever.
+        out.extend([0x21, 0xFF, 0x0B]);
+        out.extend(b"NETSCAPE2.0");
+        out.extend([0x03, 0x01, 0x00, 0x00, 0x00]);
+
+        for frame in frames {
+            out.extend([0x21, 0xF9, 0x04, 0x00, delay[0], delay[1], 0x00, 0x00]);
+            out.extend([0x2C, 0, 0, 0, 0]);
+            out.extend(width);
+            out.extend(height);
+            out.extend([0x00, 0x08]);
+
+            for block in lzw(&canvas.pixels(frame)).chunks(255) {
+                out.push(u8::try_from(block.len()).unwrap());
+                out.extend(block);
+            }
+            out.push(0);
+        }
+
+        out.push(0x3B);
+        Ok(out)
+    }
+}
+
+#[cfg(feature = "visualize")]
+impl Visualizer for Gif {
+    fn frame(&mut self, frame: Frame) {
+        self.0.frames.push(frame);
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        fs::write(&self.0.path, Self::encode(&self.0.frames)?)?;
+        println!(
+            "Stored {} frames in {}.",
+            self.0.frames.len(),
+            self.0.path.display()
+        );
+        Ok(())
+    }
+}
+
+/// Writes the pixels as 9-bit LZW literals without compressing them, which is valid as long as the
+/// code table is cleared before it grows past 9 bits.
+#[cfg(feature = "visualize")]
+fn lzw(pixels: &[u8]) -> Vec<u8> {
+    const CLEAR: u32 = 256;
+    const END: u32 = 257;
+
+    let mut out = vec![];
+    let mut bits = 0_u32;
+    let mut len = 0;
+    let mut write = |code: u32| {
+        bits |= code << len;
+        len += 9;
+        while len >= 8 {
+            out.push(bits as u8);
+            bits >>= 8;
+            len -= 8;
+        }
+    };
+
+    for chunk in pixels.chunks(250) {
+        write(CLEAR);
+        for &pixel in chunk {
+            write(u32::from(pixel));
+        }
+    }
+    write(END);
+
+    if len > 0 {
+        out.push(bits as u8);
+    }
+    out
+}
+
+/// An animated PNG with a palette.
+#[cfg(feature = "visualize")]
+struct Apng(Recording);
+
+#[cfg(feature = "visualize")]
+impl Apng {
+    fn encode(frames: &[Frame]) -> io::Result<Vec<u8>> {
+        let canvas = Canvas::new(frames);
+        let width = u32::from(pixel_size(canvas.pixel_width())?).to_be_bytes();
+        let height = u32::from(pixel_size(canvas.pixel_height())?).to_be_bytes();
+        let delay = u16::try_from(FRAME_DELAY.as_millis())
+            .unwrap()
+            .to_be_bytes();
+
+        let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+        write_chunk(
+            &mut out,
+            b"IHDR",
+            &[&width[..], &height, &[8, 3, 0, 0, 0]].concat(),
+        );
+        write_chunk(&mut out, b"PLTE", &canvas.palette.concat());
+        let frame_count = u32::try_from(frames.len()).unwrap().to_be_bytes();
+        write_chunk(&mut out, b"acTL", &[frame_count, [0; 4]].concat());
+
+        let mut sequence = 0_u32;
+        for (i, frame) in frames.iter().enumerate() {
+            let control = [
+                &sequence.to_be_bytes()[..],
+                &width,
+                &height,
+                &[0; 8],
+                &delay,
+                &1000_u16.to_be_bytes(),
+                &[0, 0],
+            ]
+            .concat();
+            write_chunk(&mut out, b"fcTL", &control);
+            sequence += 1;
+
+            let data = Self::compress(&canvas, frame)?;
+            if i == 0 {
+                write_chunk(&mut out, b"IDAT", &data);
+            } else {
+                write_chunk(
+                    &mut out,
+                    b"fdAT",
+                    &[&sequence.to_be_bytes()[..], &data].concat(),
+                );
+                sequence += 1;
+            }
+        }
+
+        write_chunk(&mut out, b"IEND", &[]);
+        Ok(out)
+    }
+
+    /// Every row starts with filter type 0, i.e. unfiltered.
+    fn compress(canvas: &Canvas, frame: &Frame) -> io::Result<Vec<u8>> {
+        let pixels = canvas.pixels(frame);
+        let mut encoder =
+            flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+
+        for row in pixels.chunks(canvas.pixel_width().max(1)) {
+            encoder.write_all(&[0])?;
+            encoder.write_all(row)?;
+        }
+
+        encoder.finish()
+    }
+}
+
+#[cfg(feature = "visualize")]
+impl Visualizer for Apng {
+    fn frame(&mut self, frame: Frame) {
+        self.0.frames.push(frame);
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        fs::write(&self.0.path, Self::encode(&self.0.frames)?)?;
+        println!(
+            "Stored {} frames in {}.",
+            self.0.frames.len(),
+            self.0.path.display()
+        );
+        Ok(())
+    }
+}
+
+#[cfg(feature = "visualize")]
+fn write_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    let mut crc = crc32fast::Hasher::new();
+    crc.update(kind);
+    crc.update(data);
+
+    out.extend(u32::try_from(data.len()).unwrap().to_be_bytes());
+    out.extend(kind);
+    out.extend(data);
+    out.extend(crc.finalize().to_be_bytes());
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Frame, VisualizeTarget};
+
+    #[test]
+    fn parses_targets() {
+        assert_eq!("terminal".parse(), Ok(VisualizeTarget::Terminal));
+        assert_eq!(
+            "out/day15.gif".parse(),
+            Ok(VisualizeTarget::Gif("out/day15.gif".into()))
+        );
+        assert_eq!(
+            "day15.PNG".parse(),
+            Ok(VisualizeTarget::Apng("day15.PNG".into()))
+        );
+        assert!("day15.txt".parse::<VisualizeTarget>().is_err());
+    }
+
+    #[test]
+    fn names_files_per_part() {
+        assert_eq!(
+            VisualizeTarget::Gif("out/day15.gif".into()).for_part(2),
+            VisualizeTarget::Gif("out/day15-part2.gif".into())
+        );
+        assert_eq!(
+            VisualizeTarget::Terminal.for_part(1),
+            VisualizeTarget::Terminal
+        );
+    }
+
+    #[test]
+    fn creates_frames_from_text() {
+        let mut frame = Frame::from_text("#..\n#\n");
+        frame.set(1, 1, b'@');
+        frame.set(5, 5, b'@');
+
+        assert_eq!(frame.width(), 3);
+        assert_eq!(frame.height(), 2);
+        assert_eq!(frame.get(1, 1), Some(b'@'));
+        assert_eq!(frame.get(3, 0), None);
+        assert_eq!(frame.to_string(), "#..\n#@ \n");
+    }
+
+    #[cfg(feature = "visualize")]
+    mod encoders {
+        use super::super::{lzw, Apng, Frame, Gif};
+
+        fn frames() -> Vec<Frame> {
+            vec![Frame::from_text("#.\n.@"), Frame::from_text("#@\n..")]
+        }
+
+        #[test]
+        fn writes_gifs() {
+            let gif = Gif::encode(&frames()).unwrap();
+            assert!(gif.starts_with(b"GIF89a"));
+            assert_eq!(gif.last(), Some(&0x3B));
+        }
+
+        #[test]
+        fn writes_literal_lzw_codes() {
+            // clear code, 0x01 and the end code as 9-bit codes.
+            assert_eq!(lzw(&[1]), vec![0x00, 0x03, 0x04, 0x04]);
+        }
+
+        #[test]
+        fn writes_apngs() {
+            let png = Apng::encode(&frames()).unwrap();
+            assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+            assert_eq!(png.windows(4).filter(|w| w == b"fcTL").count(), 2);
+            assert_eq!(png.windows(4).filter(|w| w == b"fdAT").count(), 1);
+        }
+    }
+}
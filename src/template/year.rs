@@ -0,0 +1,172 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::{config, Day, MAX_DAY};
+
+/// The first year Advent of Code took place.
+const FIRST_YEAR: u16 = 2015;
+
+/// The first event with a shortened calendar of 12 puzzles, unless configured otherwise in `aoc.toml`.
+const FIRST_SHORT_EVENT: u16 = 2025;
+
+/// A valid year of Advent of Code (i.e. an integer from 2015 onwards).
+///
+/// # Default year
+/// The year set in `AOC_YEAR` is the default year. Its solutions and data use the single-year
+/// layout of this template (`src/bin/DD.rs`, `data/inputs/DD.txt`), all other years are stored in
+/// year-qualified locations (`src/bin/YYYY-DD.rs`, `data/YYYY/inputs/DD.txt`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < FIRST_YEAR {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the number of puzzles of this year's event as configured in the `[events]` of `aoc.toml`.
+    /// By default, events up to 2024 have 25 puzzles and later events have 12.
+    pub fn puzzle_count(self) -> u8 {
+        config::get()
+            .puzzle_counts
+            .get(&self)
+            .copied()
+            .unwrap_or(if self.0 >= FIRST_SHORT_EVENT {
+                12
+            } else {
+                MAX_DAY
+            })
+    }
+
+    /// Returns the number of puzzles of the event of a year, see [`Year::or_default`].
+    /// Falls back to the largest calendar if no year is known.
+    pub fn puzzle_count_of(year: Option<Self>) -> u8 {
+        Self::or_default(year).map_or(MAX_DAY, Self::puzzle_count)
+    }
+
+    /// Returns `true` if `day` is one of the puzzles of this year's event.
+    pub fn has_day(self, day: Day) -> bool {
+        day.into_inner() <= self.puzzle_count()
+    }
+
+    /// Returns the default year as configured in `aoc.toml`, or with `AOC_YEAR`.
+    pub fn default_year() -> Option<Self> {
+        config::get()
+            .year
+            .or_else(|| env::var("AOC_YEAR").ok()?.parse().ok())
+    }
+
+    /// Returns the year to use for a command: the given year or, if absent, the default year.
+    pub fn or_default(year: Option<Self>) -> Option<Self> {
+        year.or_else(Self::default_year)
+    }
+
+    /// Returns `None` for the default year, which is stored in the single-year layout.
+    /// This is the form expected by the path helpers.
+    pub fn qualified(year: Option<Self>) -> Option<Self> {
+        year.filter(|year| Some(*year) != Self::default_year())
+    }
+
+    /// Parses the year from the name of a solution binary.
+    /// Year-qualified binaries are named `YYYY-DD`. Test executables built by cargo are named
+    /// `YYYY_DD-<hash>` instead. Binaries of the default year (`DD`) return `None`.
+    pub fn from_bin_name(name: &str) -> Option<Self> {
+        let bytes = name.as_bytes();
+        let is_qualified = bytes.len() >= 7
+            && bytes[..4].iter().all(u8::is_ascii_digit)
+            && (bytes[4] == b'-' || bytes[4] == b'_')
+            && bytes[5..7].iter().all(u8::is_ascii_digit)
+            && (bytes.len() == 7 || bytes[7] == b'-');
+
+        if is_qualified {
+            Self::new(name[..4].parse().ok()?)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the year of the currently running solution binary, see [`Year::from_bin_name`].
+    pub fn of_current_bin() -> Option<Self> {
+        let exe = env::current_exe().ok()?;
+        Self::from_bin_name(exe.file_stem()?.to_str()?)
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl PartialEq<u16> for Year {
+    fn eq(&self, other: &u16) -> bool {
+        self.0.eq(other)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year from 2015 onwards")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+    use crate::day;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2023".parse::<Year>().unwrap(), 2023);
+        assert!("2014".parse::<Year>().is_err());
+        assert!("23".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn knows_puzzle_counts() {
+        assert_eq!(Year::new(2015).unwrap().puzzle_count(), 25);
+        assert_eq!(Year::new(2024).unwrap().puzzle_count(), 25);
+        assert_eq!(Year::new(2025).unwrap().puzzle_count(), 12);
+        assert!(Year::new(2025).unwrap().has_day(day!(12)));
+        assert!(!Year::new(2025).unwrap().has_day(day!(13)));
+    }
+
+    #[test]
+    fn parses_bin_names() {
+        assert_eq!(Year::from_bin_name("2023-05"), Year::new(2023));
+        assert_eq!(Year::from_bin_name("2023_05-3f2a9c1b"), Year::new(2023));
+        assert_eq!(Year::from_bin_name("05"), None);
+        assert_eq!(Year::from_bin_name("05-3f2a9c1b"), None);
+        assert_eq!(Year::from_bin_name("advent_of_code"), None);
+    }
+}